@@ -0,0 +1,268 @@
+use core::{Core, Error, Formatter, Position, State};
+
+use std::fs;
+use std::path::PathBuf;
+
+const USAGE: &str = "\
+Usage:
+    todo [-r]                                   Open the interactive list browser. -r resets the settings.
+    todo new <file> [name]                      Create a new list.
+    todo ls <file>                              Show every entry with its path.
+    todo add <file> <path> <text>               Add an entry as the last child of the entry at path.
+    todo done <file> <path>                     Mark the entry at path and its children complete.
+    todo undone <file> <path>                   Mark the entry at path and its children incomplete.
+    todo export <file> --format txt|md|json [--output <file>]
+                                                Print the list in another format.
+    todo help                                   Show this message.
+
+Paths are the positions of the entries counting from 1 separated by \"/\", like \"2/1\".
+An empty path or \"/\" is the root of the list.";
+
+///Exit codes returned by the commands.
+const EXIT_OK: i32 = 0;
+const EXIT_ERROR: i32 = 1;
+const EXIT_USAGE: i32 = 2;
+
+enum CommandErr {
+    Usage(String),
+    Failed(String),
+}
+
+impl From<Error> for CommandErr {
+    fn from(err: Error) -> Self {
+        CommandErr::Failed(err.to_string())
+    }
+}
+
+///Runs a command given on the command line without opening the interface.
+///Returns None if the arguments are not a command, otherwise the exit code.
+pub fn run_command(args: &[String]) -> Option<i32> {
+    let command = args.first()?;
+
+    let result = match command.as_str() {
+        "help" | "-h" | "--help" => {
+            println!("{}", USAGE);
+            Ok(())
+        },
+        "new"    => new(&args[1..]),
+        "ls"     => ls(&args[1..]),
+        "add"    => add(&args[1..]),
+        "done"   => set_complete(&args[1..], true),
+        "undone" => set_complete(&args[1..], false),
+        "export" => export(&args[1..]),
+        _ => return None,
+    };
+
+    match result {
+        Ok(()) => Some(EXIT_OK),
+        Err(CommandErr::Usage(err)) => {
+            eprintln!("{}\n\n{}", err, USAGE);
+            Some(EXIT_USAGE)
+        },
+        Err(CommandErr::Failed(err)) => {
+            eprintln!("{}", err);
+            Some(EXIT_ERROR)
+        },
+    }
+}
+
+fn arg<'a>(args: &'a [String], index: usize, name: &str) -> Result<&'a str, CommandErr> {
+    match args.get(index) {
+        Some(arg) => Ok(arg),
+        None => Err(CommandErr::Usage(format!("Missing <{}>.", name))),
+    }
+}
+
+fn no_extra(args: &[String], count: usize) -> Result<(), CommandErr> {
+    match args.get(count) {
+        Some(extra) => Err(CommandErr::Usage(format!("Unexpected argument \"{}\".", extra))),
+        None => Ok(()),
+    }
+}
+
+///Turns a path like "2/1" into child indexes.
+fn parse_path(path: &str) -> Result<Vec<usize>, CommandErr> {
+    path.split('/')
+        .filter(|part| !part.is_empty())
+        .map(|part| match part.parse::<usize>() {
+            Ok(num) if num > 0 => Ok(num - 1),
+            _ => Err(CommandErr::Usage(format!("Invalid path \"{}\".", path))),
+        })
+        .collect()
+}
+
+fn format_path(path: &[usize]) -> String {
+    let parts: Vec<String> = path.iter().map(|index| (index + 1).to_string()).collect();
+    parts.join("/")
+}
+
+fn find(core: &Core, path: &str) -> Result<usize, CommandErr> {
+    core.id_at(&parse_path(path)?).map_err(|_| CommandErr::Failed(format!("No entry at path \"{}\".", path)))
+}
+
+fn load(file: &str) -> Result<Core, CommandErr> {
+    Core::load(&PathBuf::from(file)).map_err(|err| CommandErr::Failed(format!("Could not load \"{}\": {}", file, err)))
+}
+
+fn save(core: &mut Core, file: &str) -> Result<(), CommandErr> {
+    core.save_file(&PathBuf::from(file), false).map_err(|err| CommandErr::Failed(format!("Could not save \"{}\": {}", file, err)))
+}
+
+fn new(args: &[String]) -> Result<(), CommandErr> {
+    let file = arg(args, 0, "file")?;
+    no_extra(args, 2)?;
+
+    let path = PathBuf::from(file);
+    if path.exists() {
+        return Err(CommandErr::Failed(format!("\"{}\" already exists.", file)))
+    }
+
+    let name = match args.get(1) {
+        Some(name) => name.clone(),
+        None => path.file_stem().unwrap_or_default().to_string_lossy().into_owned(),
+    };
+
+    save(&mut Core::new(name), file)
+}
+
+fn ls(args: &[String]) -> Result<(), CommandErr> {
+    let file = arg(args, 0, "file")?;
+    no_extra(args, 1)?;
+
+    let core = load(file)?;
+
+    for id in core.pre_order()? {
+        let path = core.path_of(id)?;
+        let entry = core.get_entry(id)?;
+
+        let check = match core.state(id)? {
+            State::Complete   => 'X',
+            State::Partial    => '-',
+            State::Incomplete => ' ',
+        };
+
+        println!("{:<10}{}[{}] {}",
+            format_path(&path),
+            "  ".repeat(path.len()),
+            check,
+            entry.name,
+        );
+    }
+
+    Ok(())
+}
+
+fn add(args: &[String]) -> Result<(), CommandErr> {
+    let file = arg(args, 0, "file")?;
+    let path = arg(args, 1, "path")?;
+    let text = arg(args, 2, "text")?;
+    no_extra(args, 3)?;
+
+    let mut core = load(file)?;
+    let parent = find(&core, path)?;
+
+    let id = core.new_entry(String::from(text), Position::LastChild, parent)?;
+    save(&mut core, file)?;
+
+    println!("{}", format_path(&core.path_of(id)?));
+    Ok(())
+}
+
+fn set_complete(args: &[String], complete: bool) -> Result<(), CommandErr> {
+    let file = arg(args, 0, "file")?;
+    let path = arg(args, 1, "path")?;
+    no_extra(args, 2)?;
+
+    let mut core = load(file)?;
+    let id = find(&core, path)?;
+
+    if complete {
+        core.set_complete(id)?;
+    }
+    else {
+        core.set_incomplete(id)?;
+    }
+
+    save(&mut core, file)
+}
+
+fn export(args: &[String]) -> Result<(), CommandErr> {
+    let file = arg(args, 0, "file")?;
+
+    let mut format = None;
+    let mut output = None;
+
+    let mut rest = args[1..].iter();
+    while let Some(option) = rest.next() {
+        match option.as_str() {
+            "--format" | "-f" => format = rest.next(),
+            "--output" | "-o" => output = rest.next(),
+            _ => return Err(CommandErr::Usage(format!("Unexpected argument \"{}\".", option))),
+        }
+    }
+
+    let core = load(file)?;
+
+    let text = match format.map(|format| format.as_str()) {
+        Some("txt")  => to_text(&core)?,
+        Some("md")   => core.to_markdown()?,
+        Some("json") => core.to_json()?,
+        Some(format) => return Err(CommandErr::Usage(format!("Unknown format \"{}\".", format))),
+        None => return Err(CommandErr::Usage(String::from("Missing --format."))),
+    };
+
+    match output {
+        Some(output) => fs::write(output, text).map_err(|err| CommandErr::Failed(format!("Could not write \"{}\": {}", output, err))),
+        None => {
+            print!("{}", text);
+            Ok(())
+        },
+    }
+}
+
+fn to_text(core: &Core) -> Result<String, Error> {
+    let formatter = Formatter::default();
+    let mut text = String::new();
+
+    for id in core.pre_order()? {
+        let depth = core.path_of(id)?.len();
+        let has_child = !core.children_of(id)?.is_empty();
+
+        let entry = core.get_entry(id)?;
+
+        text.push_str(&formatter.format_entry(core, id, depth, has_child)?);
+        text.push('\n');
+
+        for line in formatter.format_notes(entry, depth) {
+            text.push_str(&line);
+            text.push('\n');
+        }
+    }
+
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(path: &str) -> Option<Vec<usize>> {
+        parse_path(path).ok()
+    }
+
+    #[test]
+    fn paths() {
+        //an empty path is the root of the list.
+        assert_eq!(parsed(""), Some(vec![]));
+        assert_eq!(parsed("/"), Some(vec![]));
+        assert_eq!(parsed("2/1/"), Some(vec![1, 0]));
+
+        assert_eq!(parsed("0"), None);
+        assert_eq!(parsed("2/x"), None);
+        assert_eq!(parsed("-1"), None);
+
+        assert_eq!(format_path(&[]), "");
+        assert_eq!(format_path(&[1, 0]), "2/1");
+        assert_eq!(parsed(&format_path(&[3, 0, 2])), Some(vec![3, 0, 2]));
+    }
+}
@@ -0,0 +1,25 @@
+use frames::*;
+
+use super::*;
+
+use crossterm::event::{read, Event, KeyCode, KeyEvent};
+
+///Asks a yes or no question in the prompt and waits for the answer. Escape answers no.
+pub fn confirm(question: &str, settings: &Settings, manager: &mut Manager, prompt: &mut Prompt) -> bool {
+    prompt.set_prompt(manager, format!("{}\nYes: \"y\", No: \"n\"", question));
+
+    loop {
+        manager.draw().unwrap();
+
+        match read().unwrap() {
+            Event::Mouse(_) => (),
+            Event::Resize(x, y) => { manager.resize(x, y); },
+            Event::Key(KeyEvent{code, ..}) => match code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => return true,
+                KeyCode::Char('n') | KeyCode::Char('N') => return false,
+                x if cancels(x, settings.controls().escape) => return false,
+                _ => (),
+            },
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use frames::*; 
 use crossterm::event::{KeyCode};
-use core::Type;
+use core::{Type, ProgressStyle, ProgressCount};
 
 pub trait Display {
     fn display(&self) -> String;
@@ -62,4 +62,32 @@ impl Display for Type {
             Type::Fancy => String::from("Fancy")
         }
     }
-}
\ No newline at end of file
+}
+
+impl Display for bool {
+    fn display(&self) -> String {
+        match self {
+            true  => String::from("On"),
+            false => String::from("Off"),
+        }
+    }
+}
+
+impl Display for ProgressStyle {
+    fn display(&self) -> String {
+        match self {
+            ProgressStyle::Off     => String::from("Off"),
+            ProgressStyle::Count   => String::from("Count"),
+            ProgressStyle::Percent => String::from("Percent"),
+        }
+    }
+}
+
+impl Display for ProgressCount {
+    fn display(&self) -> String {
+        match self {
+            ProgressCount::Leaves   => String::from("Leaves"),
+            ProgressCount::Children => String::from("Children"),
+        }
+    }
+}
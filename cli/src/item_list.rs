@@ -1,11 +1,11 @@
 use frames::*;
 
-use core::{Core, NodeInfo, Position, Format};
+use core::{Core, NodeInfo, Position, Error, Entry, Due, DueState, SortKey, Query, State, Fold, FileKind, write_atomic};
 use super::*;
 
-use std::{fs, io::Write};
-use std::path::PathBuf;
-use bytebuffer::*;
+use std::{fs, io::{Write, stdout}};
+use crossterm::{terminal, ExecutableCommand};
+use std::path::{Path, PathBuf};
 
 pub enum ItemControls {
     Esc,
@@ -22,6 +22,45 @@ pub enum ItemControls {
     Delete,
     Move,
     Copy,
+    Focus,
+    Unfocus,
+    Edit,
+    Undo,
+    Redo,
+    SaveAs,
+    ExportMarkdown,
+    ExportJson,
+    Notes,
+    SetDue,
+    Overdue,
+    Priority,
+    Sort,
+    Filter,
+    Search,
+    HideCompleted,
+    ToggleFold,
+    FoldAll,
+    ExpandAll,
+    InsertAbove,
+    InsertBelow,
+    InsertFirstChild,
+    MoveUp,
+    MoveDown,
+    Indent,
+    Outdent,
+}
+
+pub enum Unsaved {
+    Save,
+    Discard,
+    Cancel,
+}
+
+pub enum SearchControls {
+    Esc,
+    Previous,
+    Next,
+    NewSearch,
 }
 
 pub enum Selection {
@@ -34,10 +73,39 @@ pub enum Selection {
 pub struct Data {
     info: Vec<NodeInfo>,
     core: Core,
+    ///Where the list was loaded from or last saved to.
+    path: Option<PathBuf>,
+}
+
+impl Data {
+    ///The file the list is saved to. Lists that have never been saved go into the current dir named after the root entry.
+    fn save_path(&self) -> PathBuf {
+        match self.path {
+            Some(ref path) => path.clone(),
+            None => PathBuf::from(format!("./{}.todo", file_name(self.core.name()))),
+        }
+    }
+
+    ///The file the list can be saved to without asking. None if the list has no path and the default one is taken.
+    fn known_path(&self) -> Option<PathBuf> {
+        match self.path {
+            Some(ref path) => Some(path.clone()),
+            None => Some(self.save_path()).filter(|path| !path.exists()),
+        }
+    }
+
+    ///The file the list is printed to, next to the saved list.
+    fn print_path(&self) -> PathBuf {
+        self.save_path().with_extension("txt")
+    }
 }
 
 pub struct ItemList {
     ui: ListUi,
+    notes: NoteEditor,
+    jump: JumpList,
+    ///Entries found by the current search which are highlighted.
+    matches: Vec<usize>,
 }
 
 impl ItemList {
@@ -47,62 +115,162 @@ impl ItemList {
 
         ItemList{
             ui: ui,
+            notes: NoteEditor::new(manager, colors),
+            jump: JumpList::new(manager, colors),
+            matches: Vec::new(),
         }
     }
 
-    pub fn new_list(&mut self, name: String) -> Data{
+    pub fn new_list(&mut self, name: String) -> Result<Data, Error> {
         //self.ui.pointer = 0;
         self.ui.set_pointer_no_update(0);
         let core = Core::new(name);
 
-        Data{
-            info: core.get_entries_info(),
+        Ok(Data{
+            info: core.get_entries_info()?,
             core: core,
-        }
+            path: None,
+        })
     }
 
-    pub fn load_list(&mut self, name: &PathBuf) -> Result<Data, ByteErr> {
+    pub fn load_list(&mut self, name: &PathBuf) -> Result<Data, Error> {
         //self.ui.pointer = 0;
         self.ui.set_pointer_no_update(0);
         let core = Core::load(name)?;
 
         Ok(Data{
-            info: core.get_entries_info(),
+            info: core.get_entries_info()?,
             core: core,
+            path: todo_path(name),
         })
     }
 
+    ///Loads the list at path or starts a new one that will be saved there if the file does not exist yet.
+    pub fn open_file(&mut self, path: &PathBuf) -> Result<Data, Error> {
+        if path.exists() {
+            return self.load_list(path)
+        }
+
+        let mut path = path.clone();
+        if path.extension().is_none() {
+            path.set_extension("todo");
+        }
+
+        if path.exists() {
+            return self.load_list(&path)
+        }
+
+        let name = path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+        let mut data = self.new_list(name)?;
+        data.path = todo_path(&path);
+
+        Ok(data)
+    }
+
     pub fn main(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt) {
         use ItemControls::*;
 
         self.ui.enable();
-        self.format_entries(data, settings, manager);
-        prompt.set_prompt(manager, settings.controls().help_tip());
+        let result = self.format_entries(data, settings, manager)
+            .and_then(|_| self.set_status(data, settings, manager, prompt));
+        self.report(result, settings, manager, prompt);
 
         loop {
+            self.set_title(data);
             manager.draw().unwrap();
 
-            match settings.controls().item(manager) {
-                Esc             => break,
-                PointerUp       => self.pointer_up(manager, settings),
-                PointerDown     => self.pointer_down(manager, settings),
+            let result = match settings.controls().item(manager) {
+                Esc             => match self.confirm_leave(data, settings, manager, prompt) {
+                    Ok(true) => break,
+                    result   => result.map(|_| ()),
+                },
+                PointerUp       => Ok(self.pointer_up(manager, settings)),
+                PointerDown     => Ok(self.pointer_down(manager, settings)),
                 Select          => self.select(data, settings, manager, prompt),
                 IncDepth        => self.inc_depth(data, settings, manager),
                 DecDepth        => self.dec_depth(data, settings, manager),
                 Toggle          => self.toggle(data, settings, manager),
-                Save            => save(data),
-                Help            => self.help(settings, manager, prompt),
+                Save            => self.save_list(data, settings, manager, prompt),
+                SaveAs          => self.save_as(data, settings, manager, prompt),
+                Help            => self.help(data, settings, manager, prompt),
                 Print           => self.print(data, settings),
                 PrintUnfinished => self.print_unfinished(data, settings),
-                Delete          => self.delete(data, settings, manager),
+                ExportMarkdown  => self.export_markdown(data, settings, manager, prompt),
+                ExportJson      => self.export_json(data, settings, manager, prompt),
+                Delete          => self.delete(data, settings, manager, prompt),
                 Move            => self.move_entry(data, settings, manager, prompt),
                 Copy            => self.copy_entry(data, settings, manager, prompt),
-            }
+                Focus           => self.focus(data, settings, manager, prompt),
+                Unfocus         => self.unfocus(data, settings, manager, prompt),
+                Edit            => self.edit(data, settings, manager, prompt),
+                Undo            => self.undo(data, settings, manager, prompt),
+                Redo            => self.redo(data, settings, manager, prompt),
+                Notes           => self.notes(data, settings, manager, prompt),
+                SetDue          => self.set_due(data, settings, manager, prompt),
+                Overdue         => self.overdue(data, settings, manager, prompt),
+                Priority        => self.priority(data, settings, manager, prompt),
+                Sort            => self.sort(data, settings, manager, prompt),
+                Filter          => self.filter(data, settings, manager, prompt),
+                Search          => self.search(data, settings, manager, prompt),
+                HideCompleted   => self.hide_completed(data, settings, manager, prompt),
+                ToggleFold      => self.toggle_fold(data, settings, manager),
+                FoldAll         => self.fold_all(data, settings, manager, Fold::Collapsed),
+                ExpandAll       => self.fold_all(data, settings, manager, Fold::Expanded),
+                InsertAbove     => self.insert(data, settings, manager, prompt, Position::SiblingBefore),
+                InsertBelow     => self.insert(data, settings, manager, prompt, Position::SiblingAfter),
+                InsertFirstChild => self.insert(data, settings, manager, prompt, Position::FirstChild),
+                MoveUp          => self.shift(data, settings, manager, Core::move_up),
+                MoveDown        => self.shift(data, settings, manager, Core::move_down),
+                Indent          => self.indent(data, settings, manager),
+                Outdent         => self.shift(data, settings, manager, Core::outdent),
+            };
+
+            let result = result.and_then(|_| self.autosave(data, settings));
+            self.report(result, settings, manager, prompt);
         }
 
+        stdout().execute(terminal::SetTitle("todo")).unwrap();
         self.ui.disable();
     }
 
+    ///Marks the title when the list has unsaved changes.
+    fn set_title(&mut self, data: &Data) {
+        let marker = if data.core.is_modified() { "*" } else { "" };
+        stdout().execute(terminal::SetTitle(format!("todo - {}{}", data.core.name(), marker))).unwrap();
+    }
+
+    ///Asks what to do with unsaved changes before leaving the list. Returns false if the user wants to stay.
+    fn confirm_leave(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt) -> Result<bool, Error> {
+        if !data.core.is_modified() { return Ok(true) }
+
+        prompt.set_prompt(manager, settings.controls().unsaved_prompt());
+        manager.draw().unwrap();
+
+        match settings.controls().unsaved(manager) {
+            Unsaved::Save    => self.save_list(data, settings, manager, prompt).map(|_| !data.core.is_modified()),
+            Unsaved::Discard => Ok(true),
+            Unsaved::Cancel  => self.set_status(data, settings, manager, prompt).map(|_| false),
+        }
+    }
+
+    fn autosave(&mut self, data: &mut Data, settings: &Settings) -> Result<(), Error> {
+        if settings.options().autosave && data.core.is_modified() {
+            if let Some(path) = data.known_path() {
+                data.core.save_file(&path, settings.options().backup)?;
+                data.path = Some(path);
+            }
+        }
+
+        Ok(())
+    }
+
+    ///Shows the error in the prompt if the action failed.
+    fn report(&mut self, result: Result<(), Error>, settings: &Settings, manager: &mut Manager, prompt: &mut Prompt) {
+        if let Err(err) = result {
+            prompt.set_prompt(manager, format!("{}\n{}", err, settings.controls().help_tip()));
+        }
+    }
+
     fn pointer_up(&mut self, manager: &mut Manager, settings: &mut Settings) {
         self.ui.dec_pointer(manager, &settings.colors(), 1);
     }
@@ -111,29 +279,309 @@ impl ItemList {
         self.ui.inc_pointer(manager, &settings.colors(), 1);
     }
 
-    fn format_entries(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager) {
+    fn format_entries(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager) -> Result<(), Error> {
         self.ui.clear();
         let formatter = settings.formatter();
 
-        for item in &data.info {
-            self.ui.push(formatter.format(data.core.get_entry(item.id), item.depth, item.child_count > 0))
+        for (i, item) in data.info.iter().enumerate() {
+            let entry = data.core.get_entry(item.id)?;
+
+            self.ui.push(formatter.format_entry(&data.core, item.id, item.depth, item.child_count > 0)?);
+
+            if self.matches.contains(&item.id) {
+                self.ui.mark(i, Some(settings.colors().search));
+            }
+            else if let Some(color) = due_color(entry, &settings.colors()) {
+                self.ui.mark(i, Some(color));
+            }
+            else if data.core.state(item.id)? == State::Partial {
+                self.ui.mark(i, Some(settings.colors().partial));
+            }
         }
 
         self.ui.refresh_colors(&settings.colors(), manager);
+        Ok(())
     }
 
-    fn select(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt) {
-        let name = prompt.get_string(manager, String::from("Name of new child"));
-        data.core.new_entry(name, Position::LastChild, data.info[self.ui.pointer()].id);
+    ///Shows the path to the focused entry above the control tips.
+    fn set_status(&mut self, data: &Data, settings: &Settings, manager: &mut Manager, prompt: &mut Prompt) -> Result<(), Error> {
+        let mut path = Vec::new();
+
+        for id in data.core.root_path()? {
+            path.push(data.core.get_entry(id)?.name.as_str());
+        }
+
+        let mut view = String::new();
 
-        data.info = data.core.get_entries_info();
+        if let Some(tag) = data.core.filter() {
+            view.push_str(&format!("    Showing #{}", tag));
+        }
+
+        if data.core.hide_completed() {
+            view.push_str("    Completed hidden");
+        }
+
+        prompt.set_prompt(manager, format!("{}{}\n{}", path.join(" > "), view, settings.controls().item_prompt()));
+        Ok(())
+    }
+
+    fn select(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt) -> Result<(), Error> {
+        let name = match prompt.get_string(manager, String::from("Name of new child")) {
+            Some(name) if !name.is_empty() => name,
+            _ => return self.set_status(data, settings, manager, prompt),
+        };
+        data.core.new_entry(name, Position::LastChild, data.info[self.ui.pointer()].id)?;
+
+        data.info = data.core.get_entries_info()?;
         
-        self.format_entries(data, settings, manager);
-        prompt.set_prompt(manager, settings.controls().item_prompt());
+        self.format_entries(data, settings, manager)?;
+        self.set_status(data, settings, manager, prompt)
+    }
+
+    ///Adds an entry next to or under the selected one and moves the pointer to it so entries can be added one after another.
+    fn insert(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt, pos: Position) -> Result<(), Error> {
+        let id = data.info[self.ui.pointer()].id;
+
+        //the current root has no siblings that are shown.
+        if pos != Position::FirstChild && id == data.core.current_root() {
+            return Ok(())
+        }
+
+        let name = match prompt.get_string(manager, String::from("Name of new entry")) {
+            Some(name) if !name.is_empty() => name,
+            _ => return self.set_status(data, settings, manager, prompt),
+        };
+        let new_id = data.core.new_entry(name, pos, id)?;
+
+        if pos == Position::FirstChild {
+            data.core.set_fold(id, Fold::Expanded)?;
+        }
+
+        self.find_new_id_pos(new_id, data, settings, manager)?;
+        self.set_status(data, settings, manager, prompt)
+    }
+
+    fn edit(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt) -> Result<(), Error> {
+        let id = data.info[self.ui.pointer()].id;
+        let name = match prompt.edit_string(manager, String::from("New name of entry."), &data.core.get_entry(id)?.name) {
+            Some(name) => name,
+            None => return self.set_status(data, settings, manager, prompt),
+        };
+        data.core.rename(id, name)?;
+
+        self.format_entries(data, settings, manager)?;
+        self.set_status(data, settings, manager, prompt)
+    }
+
+    ///Opens the notes of the selected entry in the note editor.
+    fn notes(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt) -> Result<(), Error> {
+        let id = data.info[self.ui.pointer()].id;
+        let entry = data.core.get_entry(id)?.clone();
+
+        self.ui.disable();
+        let notes = self.notes.main(&entry.name, &entry.notes, settings, manager, prompt);
+        self.ui.enable();
+
+        data.core.set_notes(id, notes)?;
+
+        self.format_entries(data, settings, manager)?;
+        self.set_status(data, settings, manager, prompt)
+    }
+
+    fn set_due(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt) -> Result<(), Error> {
+        let id = data.info[self.ui.pointer()].id;
+        let input = match prompt.get_string(manager, String::from("Due date as YYYY-MM-DD with an optional HH:MM time. Enter \"none\" to remove it.")) {
+            Some(input) if !input.is_empty() => input,
+            _ => return self.set_status(data, settings, manager, prompt),
+        };
+
+        let due = if input.eq_ignore_ascii_case("none") {
+            None
+        }
+        else {
+            Some(Due::parse(&input).ok_or(Error::InvalidDue(input))?)
+        };
+
+        data.core.set_due(id, due)?;
+
+        self.format_entries(data, settings, manager)?;
+        self.set_status(data, settings, manager, prompt)
     }
 
-    fn find_new_id_pos(&mut self, id: usize, data: &mut Data, settings: &mut Settings, manager: &mut Manager) {
-        data.info = data.core.get_entries_info();
+    fn priority(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt) -> Result<(), Error> {
+        let id = data.info[self.ui.pointer()].id;
+        let priority = data.core.get_entry(id)?.priority.next();
+        data.core.set_priority(id, priority)?;
+
+        self.format_entries(data, settings, manager)?;
+        self.set_status(data, settings, manager, prompt)
+    }
+
+    ///Sorts the children of the selected entry.
+    fn sort(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt) -> Result<(), Error> {
+        let id = data.info[self.ui.pointer()].id;
+        let input = prompt.get_string(manager, String::from("Sort children by priority, name, completion or due. Add \"all\" to sort every level below too, like \"name all\".")).unwrap_or_default();
+
+        let mut words = input.split_whitespace();
+        let key = match words.next().and_then(SortKey::parse) {
+            Some(key) => key,
+            None => return self.set_status(data, settings, manager, prompt),
+        };
+        let recursive = words.next().map_or(false, |word| word.eq_ignore_ascii_case("all"));
+
+        data.core.sort_children(id, key, recursive)?;
+
+        self.find_new_id_pos(id, data, settings, manager)?;
+        self.set_status(data, settings, manager, prompt)
+    }
+
+    ///Shows only the entries with a tag. Entering nothing shows every entry again.
+    fn filter(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt) -> Result<(), Error> {
+        let id = data.info[self.ui.pointer()].id;
+
+        let tags: Vec<String> = data.core.tags()?.into_iter().map(|(tag, count)| format!("#{} ({})", tag, count)).collect();
+        let input = match prompt.get_string(manager, format!("Tags: {}\nTag to show or nothing to show every entry.", tags.join(", "))) {
+            Some(input) => input,
+            None => return self.set_status(data, settings, manager, prompt),
+        };
+
+        data.core.set_filter(if input.is_empty() { None } else { Some(input) });
+
+        self.find_new_id_pos(id, data, settings, manager)?;
+        self.set_status(data, settings, manager, prompt)
+    }
+
+    ///Toggles showing completed entries. If the selected entry is hidden the pointer moves to the closest shown entry above it.
+    fn hide_completed(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt) -> Result<(), Error> {
+        let above: Vec<usize> = data.info[..=self.ui.pointer()].iter().rev().map(|item| item.id).collect();
+
+        let hide = !data.core.hide_completed();
+        data.core.set_hide_completed(hide);
+        data.info = data.core.get_entries_info()?;
+
+        //the root is always shown so there is always an entry to go to.
+        let id = above.into_iter().find(|id| data.info.iter().any(|item| item.id == *id)).unwrap_or(data.info[0].id);
+
+        self.find_new_id_pos(id, data, settings, manager)?;
+        self.set_status(data, settings, manager, prompt)
+    }
+
+    ///Finds entries anywhere in the list and moves between them until the user is done.
+    fn search(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt) -> Result<(), Error> {
+        let found = self.search_loop(data, settings, manager, prompt);
+
+        self.matches.clear();
+        self.format_entries(data, settings, manager)?;
+
+        if found? {
+            self.set_status(data, settings, manager, prompt)?;
+        }
+
+        Ok(())
+    }
+
+    ///Returns false if nothing matched, leaving the message in the prompt.
+    fn search_loop(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt) -> Result<bool, Error> {
+        use SearchControls::*;
+
+        let mut input = String::new();
+        let mut current = 0;
+
+        loop {
+            if input.is_empty() {
+                input = prompt.get_string(manager, String::from("Search for text or a /regex/.")).unwrap_or_default();
+                if input.is_empty() { return Ok(true) }
+
+                self.matches = data.core.search(&Query::parse(&input)?)?;
+                current = 0;
+
+                if self.matches.is_empty() {
+                    prompt.set_prompt(manager, format!("Nothing matches \"{}\".\n{}", input, settings.controls().help_tip()));
+                    return Ok(false)
+                }
+            }
+
+            let id = self.matches[current];
+            data.core.reveal(id)?;
+            self.find_new_id_pos(id, data, settings, manager)?;
+
+            prompt.set_prompt(manager, format!("Match {} of {} for \"{}\"\n{}", current + 1, self.matches.len(), input, settings.controls().search_prompt()));
+            manager.draw().unwrap();
+
+            match settings.controls().search_mode(manager) {
+                Esc       => return Ok(true),
+                Previous  => current = (current + self.matches.len() - 1) % self.matches.len(),
+                Next      => current = (current + 1) % self.matches.len(),
+                NewSearch => input.clear(),
+            }
+        }
+    }
+
+    ///Lists every overdue entry in the list and moves the pointer to the one picked.
+    fn overdue(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt) -> Result<(), Error> {
+        let ids = data.core.overdue()?;
+
+        self.ui.disable();
+        let picked = self.jump.main("Overdue entries.", &ids, &data.core, settings, manager, prompt);
+        self.ui.enable();
+
+        if let Some(id) = picked? {
+            data.core.reveal(id)?;
+            self.find_new_id_pos(id, data, settings, manager)?;
+        }
+        else {
+            self.ui.update(manager);
+        }
+
+        self.set_status(data, settings, manager, prompt)
+    }
+
+    fn undo(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt) -> Result<(), Error> {
+        if let Some(id) = data.core.undo()? {
+            self.find_new_id_pos(id, data, settings, manager)?;
+            self.set_status(data, settings, manager, prompt)?;
+        }
+
+        Ok(())
+    }
+
+    fn redo(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt) -> Result<(), Error> {
+        if let Some(id) = data.core.redo()? {
+            self.find_new_id_pos(id, data, settings, manager)?;
+            self.set_status(data, settings, manager, prompt)?;
+        }
+
+        Ok(())
+    }
+
+    fn focus(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt) -> Result<(), Error> {
+        data.core.push_root(data.info[self.ui.pointer()].id)?;
+
+        self.ui.set_pointer_no_update(0);
+        data.info = data.core.get_entries_info()?;
+
+        self.format_entries(data, settings, manager)?;
+        self.set_status(data, settings, manager, prompt)
+    }
+
+    fn unfocus(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt) -> Result<(), Error> {
+        if let Some(id) = data.core.pop_root() {
+            //the old root may be deeper than the current depth so start from the top in case it is not shown.
+            self.ui.set_pointer_no_update(0);
+            self.find_new_id_pos(id, data, settings, manager)?;
+            self.set_status(data, settings, manager, prompt)?;
+        }
+
+        Ok(())
+    }
+
+    fn find_new_id_pos(&mut self, id: usize, data: &mut Data, settings: &mut Settings, manager: &mut Manager) -> Result<(), Error> {
+        data.info = data.core.get_entries_info()?;
+
+        //keep the pointer on the list if the entry is not shown.
+        if self.ui.pointer() >= data.info.len() {
+            self.ui.set_pointer_no_update(data.info.len() - 1);
+        }
 
         for (i, node) in data.info.iter().enumerate(){
             if node.id == id {
@@ -143,72 +591,156 @@ impl ItemList {
             }
         }
 
-        self.format_entries(data, settings, manager);
+        self.format_entries(data, settings, manager)
     }
 
-    fn inc_depth(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager){
+    fn inc_depth(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager) -> Result<(), Error> {
         data.core.inc_depth(1);
         let id = data.info[self.ui.pointer()].id;
 
-        self.find_new_id_pos(id, data, settings, manager);
+        self.find_new_id_pos(id, data, settings, manager)
     }
 
-    fn dec_depth(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager){
+    fn dec_depth(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager) -> Result<(), Error> {
         data.core.dec_depth(1);
         let current = data.info[self.ui.pointer()];
 
         let id = if current.depth > data.core.depth() {
-            data.core.parent_id(current.id)
+            data.core.parent_id(current.id)?
         }
         else {
             current.id
         };
 
-        self.find_new_id_pos(id, data, settings, manager);
+        self.find_new_id_pos(id, data, settings, manager)
+    }
+
+    fn toggle_fold(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager) -> Result<(), Error> {
+        let current = data.info[self.ui.pointer()];
+        data.core.toggle_fold(current.id, current.depth)?;
+
+        self.find_new_id_pos(current.id, data, settings, manager)
+    }
+
+    fn fold_all(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, fold: Fold) -> Result<(), Error> {
+        let ancestors = data.core.ancestors(data.info[self.ui.pointer()].id)?;
+
+        data.core.fold_all(fold)?;
+        data.info = data.core.get_entries_info()?;
+
+        //stay on the closest entry the selected one is under if it is folded away.
+        let id = ancestors.into_iter().rev().find(|id| data.info.iter().any(|item| item.id == *id)).unwrap_or(data.info[0].id);
+
+        self.find_new_id_pos(id, data, settings, manager)
     }
 
-    fn toggle(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager) {
-        data.core.toggle_comp(data.info[self.ui.pointer()].id);
-        data.info = data.core.get_entries_info();
-        self.format_entries(data, settings, manager);
+    fn toggle(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager) -> Result<(), Error> {
+        let id = data.info[self.ui.pointer()].id;
+        data.core.toggle_comp(id)?;
+
+        //the entry may have been hidden by completing it.
+        self.find_new_id_pos(id, data, settings, manager)
     }
 
-    fn help(&mut self, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt) {
+    fn help(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt) -> Result<(), Error> {
         self.ui.disable();
         settings.help_menu(manager, prompt, HelpMenuType::Item);
         self.ui.enable();
         self.ui.update(manager);
-        prompt.set_prompt(manager, settings.controls().item_prompt());
+        self.set_status(data, settings, manager, prompt)
     }
 
-    fn print(&mut self, data: &mut Data, settings: &mut Settings) {
-        let mut file = fs::OpenOptions::new().write(true).create(true).truncate(true).open(format!("./{}.txt", data.core.name())).unwrap();
+    fn print(&mut self, data: &mut Data, settings: &mut Settings) -> Result<(), Error> {
+        let mut file = fs::OpenOptions::new().write(true).create(true).truncate(true).open(data.print_path())?;
         let formatter = settings.print_formatter();
 
         for item in &data.info {
-            write!(file, "{}\n", formatter.format(&data.core.get_entry(item.id), item.depth, item.child_count > 0)).unwrap();
+            let entry = data.core.get_entry(item.id)?;
+            write!(file, "{}\n", formatter.format_entry(&data.core, item.id, item.depth, item.child_count > 0)?)?;
+
+            for line in formatter.format_notes(entry, item.depth) {
+                write!(file, "{}\n", line)?;
+            }
         }
+
+        Ok(())
     }
 
-    fn print_unfinished(&mut self, data: &mut Data, settings: &mut Settings) {
-        let mut file = fs::OpenOptions::new().write(true).create(true).truncate(true).open(format!("./{}.txt", data.core.name())).unwrap();
+    fn print_unfinished(&mut self, data: &mut Data, settings: &mut Settings) -> Result<(), Error> {
+        let mut file = fs::OpenOptions::new().write(true).create(true).truncate(true).open(data.print_path())?;
         let formatter = settings.print_formatter();
 
         for item in &data.info {
-            let entry = data.core.get_entry(item.id);
+            let entry = data.core.get_entry(item.id)?;
 
             if !entry.complete {
-                write!(file, "{}\n", formatter.format(entry, item.depth, item.child_count > 0)).unwrap();
+                write!(file, "{}\n", formatter.format_entry(&data.core, item.id, item.depth, item.child_count > 0)?)?;
+
+                for line in formatter.format_notes(entry, item.depth) {
+                    write!(file, "{}\n", line)?;
+                }
             }
         }
+
+        Ok(())
+    }
+
+    fn export_markdown(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt) -> Result<(), Error> {
+        let path = data.save_path().with_extension("md");
+        write_atomic(&path, data.core.to_markdown()?.as_bytes(), false)?;
+
+        prompt.set_prompt(manager, format!("Exported to {}.\n{}", path.display(), settings.controls().help_tip()));
+        Ok(())
     }
 
-    fn delete(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager){
-        if data.info[self.ui.pointer()].id != data.core.current_root() {
-            data.core.delete(data.info[self.ui.pointer()].id);
+    fn export_json(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt) -> Result<(), Error> {
+        let path = data.save_path().with_extension("json");
+        write_atomic(&path, data.core.to_json()?.as_bytes(), false)?;
+
+        prompt.set_prompt(manager, format!("Exported to {}.\n{}", path.display(), settings.controls().help_tip()));
+        Ok(())
+    }
+
+    ///Saves to the list's path or asks for one if saving there would replace another file.
+    fn save_list(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt) -> Result<(), Error> {
+        match data.known_path() {
+            Some(path) => save(data, settings, manager, prompt, path),
+            None => self.save_as(data, settings, manager, prompt),
+        }
+    }
+
+    fn save_as(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt) -> Result<(), Error> {
+        let input = match prompt.get_string(manager, String::from("Save list as.")) {
+            Some(input) if !input.is_empty() => input,
+            _ => return self.set_status(data, settings, manager, prompt),
+        };
+
+        let mut path = PathBuf::from(input);
+        if path.extension().is_none() {
+            path.set_extension("todo");
+        }
+
+        save(data, settings, manager, prompt, path)
+    }
+
+    ///Asks before deleting an entry with children.
+    fn delete(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt) -> Result<(), Error> {
+        let id = data.info[self.ui.pointer()].id;
+        if id == data.core.current_root() { return Ok(()) }
+
+        //the entry itself is one of its descendants.
+        let below = data.core.descendants_of(id)?.len() - 1;
 
-            self.find_new_id_pos(data.info[self.ui.pointer() - 1].id, data, settings, manager);
+        if below > 0 {
+            let question = format!("Delete \"{}\" and the {} entries below it?", data.core.get_entry(id)?.name, below);
+            let answer = confirm(&question, settings, manager, prompt);
+
+            self.set_status(data, settings, manager, prompt)?;
+            if !answer { return Ok(()) }
         }
+
+        data.core.delete(id)?;
+        self.find_new_id_pos(data.info[self.ui.pointer() - 1].id, data, settings, manager)
     }
 
     fn get_position(&mut self, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt) -> Position {
@@ -273,40 +805,86 @@ impl ItemList {
         return ret
     }
 
-    fn move_entry(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt){
+    fn move_entry(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt) -> Result<(), Error> {
         if self.ui.pointer() != 0 {
             self.ui.select();
-            let children = SubTree::new(data.core.descendants_of(self.ui.selected().unwrap()), &data.info);
+            let children = SubTree::new(data.core.descendants_of(data.info[self.ui.selected().unwrap()].id)?, &data.info);
 
             if let Some(temp) = self.multi_select(settings, manager, prompt, Some(children)){
-                data.core.move_entry(data.info[temp.0].id, temp.1, data.info[temp.2].id);
-                self.find_new_id_pos(data.info[self.ui.pointer()].id, data, settings, manager);
+                data.core.move_entry(data.info[temp.0].id, temp.1, data.info[temp.2].id)?;
+                self.find_new_id_pos(data.info[self.ui.pointer()].id, data, settings, manager)?;
             }
             else{
                 self.ui.refresh_colors(&settings.colors(), manager);
                 manager.draw().unwrap();
             }
+
+            self.set_status(data, settings, manager, prompt)?;
         }
+
+        Ok(())
+    }
+
+    ///Moves the selected entry one step and keeps the pointer on it.
+    fn shift(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, step: fn(&mut Core, usize) -> Result<(), Error>) -> Result<(), Error> {
+        let id = data.info[self.ui.pointer()].id;
+        step(&mut data.core, id)?;
+
+        self.find_new_id_pos(id, data, settings, manager)
     }
 
-    fn copy_entry(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt){
+    ///The new parent is unfolded so the entry stays in view.
+    fn indent(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager) -> Result<(), Error> {
+        let id = data.info[self.ui.pointer()].id;
+
+        if let Some(parent) = data.core.indent(id)? {
+            data.core.set_fold(parent, Fold::Expanded)?;
+        }
+
+        self.find_new_id_pos(id, data, settings, manager)
+    }
+
+    fn copy_entry(&mut self, data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt) -> Result<(), Error> {
         self.ui.select();
         
         if let Some(temp) = self.multi_select(settings, manager, prompt, None){
-            data.core.copy_entry(data.info[temp.0].id, temp.1, data.info[temp.2].id);
-            self.find_new_id_pos(data.info[self.ui.pointer()].id, data, settings, manager);
+            data.core.copy_entry(data.info[temp.0].id, temp.1, data.info[temp.2].id)?;
+            self.find_new_id_pos(data.info[self.ui.pointer()].id, data, settings, manager)?;
         }
         else{
             self.ui.refresh_colors(&settings.colors(), manager);
             manager.draw().unwrap();
         }
+
+        self.set_status(data, settings, manager, prompt)
     }
 }
 
-fn save(data: &mut Data) {
-    let mut file = fs::OpenOptions::new().write(true).create(true).open(format!("./{}.todo", data.core.name())).unwrap();
+///Saves the list to the path, which becomes the list's path once the save succeeds.
+fn save(data: &mut Data, settings: &mut Settings, manager: &mut Manager, prompt: &mut Prompt, path: PathBuf) -> Result<(), Error> {
+    data.core.save_file(&path, settings.options().backup)?;
 
-    file.write_bytes(&mut data.core.save(), 1024).unwrap();
+    prompt.set_prompt(manager, format!("Saved to {}.\n{}", path.display(), settings.controls().help_tip()));
+    data.path = Some(path);
+    Ok(())
+}
+
+///Turns the name of a list into something that can be used as a file name.
+fn file_name(name: &str) -> String {
+    let name: String = name.trim().chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+
+    if name.is_empty() || name.chars().all(|c| c == '.') {
+        String::from("todo")
+    }
+    else {
+        name
+    }
 }
 
 struct SubTree<'a>{
@@ -329,4 +907,24 @@ impl<'a> SubTree<'a> {
 
         return false
     }
-}
\ No newline at end of file
+}
+
+///Lists imported from markdown or json are saved to a todo file next to the file they came from.
+///None if that file already exists, so it is not replaced by the imported list.
+fn todo_path(path: &Path) -> Option<PathBuf> {
+    match FileKind::of(path) {
+        Some(FileKind::Markdown) | Some(FileKind::Json) => Some(path.with_extension("todo")).filter(|path| !path.exists()),
+        _ => Some(path.to_path_buf()),
+    }
+}
+
+///Color of entries that are due soon. Complete entries keep the default color.
+pub fn due_color(entry: &Entry, colors: &Colors) -> Option<Color> {
+    if entry.complete { return None }
+
+    match entry.due_state()? {
+        DueState::Overdue => Some(colors.overdue),
+        DueState::Today   => Some(colors.due_today),
+        DueState::Later   => None,
+    }
+}
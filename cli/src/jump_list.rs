@@ -0,0 +1,77 @@
+use frames::*;
+
+use core::{Core, Error};
+use super::*;
+
+///Lists entries from anywhere in the tree with the path to each of them so one can be picked to jump to.
+pub struct JumpList {
+    ui: ListUi,
+}
+
+impl JumpList {
+    pub fn new(manager: &mut Manager, colors: &Colors) -> JumpList {
+        let mut ui = ListUi::new(manager, Some(Box::new(MainUiUpdate{})), colors);
+        ui.disable();
+
+        JumpList{
+            ui: ui,
+        }
+    }
+
+    ///Shows the entries until one is picked or the user exits. Returns the id of the picked entry.
+    pub fn main(&mut self, title: &str, ids: &[usize], core: &Core, settings: &Settings, manager: &mut Manager, prompt: &mut Prompt) -> Result<Option<usize>, Error> {
+        use Selection::*;
+
+        self.write_entries(ids, core, settings, manager)?;
+        self.ui.enable();
+
+        let picked = loop {
+            prompt.set_prompt(manager, format!("{}\n{}", title, settings.controls().select_prompt()));
+            manager.draw().unwrap();
+
+            match settings.controls().multi_select(manager) {
+                Esc         => break None,
+                PointerUp   => self.ui.dec_pointer(manager, &settings.colors(), 1),
+                PointerDown => self.ui.inc_pointer(manager, &settings.colors(), 1),
+                Select      => {
+                    if let Some(&id) = ids.get(self.ui.pointer()) {
+                        break Some(id)
+                    }
+                },
+            }
+        };
+
+        self.ui.disable();
+        Ok(picked)
+    }
+
+    fn write_entries(&mut self, ids: &[usize], core: &Core, settings: &Settings, manager: &mut Manager) -> Result<(), Error> {
+        self.ui.clear();
+        self.ui.set_pointer_no_update(0);
+
+        for &id in ids {
+            let mut path = Vec::new();
+            for parent in core.ancestors(id)? {
+                if parent != id {
+                    path.push(core.get_entry(parent)?.name.as_str());
+                }
+            }
+
+            let entry = core.get_entry(id)?;
+            let due = match entry.due {
+                Some(due) => format!(" (due {})", due),
+                None => String::new(),
+            };
+
+            self.ui.push(format!("{}{}    in {}", entry.name, due, path.join(" > ")));
+            self.ui.mark(self.ui.len() - 1, due_color(entry, &settings.colors()));
+        }
+
+        if ids.is_empty() {
+            self.ui.push(String::from("Nothing here."));
+        }
+
+        self.ui.refresh_colors(&settings.colors(), manager);
+        Ok(())
+    }
+}
@@ -26,8 +26,17 @@ use list_ui::*;
 mod item_list;
 use item_list::*;
 
+mod notes;
+use notes::*;
+
+mod jump_list;
+use jump_list::*;
+
 mod prompt;
-use prompt::Prompt;
+use prompt::{Prompt, cancels};
+
+mod confirm;
+use confirm::confirm;
 
 mod display;
 use display::*;
@@ -36,6 +45,9 @@ mod todo;
 use todo::*;
 pub use todo::Todo;
 
+mod commands;
+pub use commands::run_command;
+
 use frames::{SizeUpdate, Coord};
 
 pub struct MainUiUpdate{}
@@ -7,6 +7,8 @@ pub struct ListUi {
     frame: Rc<RefCell<frame_types::Text>>,
     pointer: usize,
     selected: Option<usize>,
+    ///Rows shown in their own color instead of the default color.
+    marks: Vec<Option<Color>>,
 }
 
 impl ListUi {
@@ -22,6 +24,7 @@ impl ListUi {
             frame: frame,
             pointer: 0,
             selected: None,
+            marks: Vec::new(),
         }
     }
 
@@ -63,7 +66,7 @@ impl ListUi {
             self.frame.borrow_mut().set_fg(self.pointer, colors.selected);
         }
         else{
-            self.frame.borrow_mut().set_fg(self.pointer, colors.default);
+            self.frame.borrow_mut().set_fg(self.pointer, self.row_color(self.pointer, colors));
         }
         
         self.frame.borrow_mut().set_fg(new, colors.pointer);
@@ -129,6 +132,22 @@ impl ListUi {
         self.frame.borrow_mut().set_bg(index, color);
     }
 
+    ///Gives the row its own color that is kept when the colors are refreshed. None returns it to the default color.
+    pub fn mark(&mut self, index: usize, color: Option<Color>){
+        if self.marks.len() <= index {
+            self.marks.resize(index + 1, None);
+        }
+        self.marks[index] = color;
+
+        if let Some(color) = color {
+            self.frame.borrow_mut().set_fg(index, color);
+        }
+    }
+
+    fn row_color(&self, index: usize, colors: &Colors) -> Color {
+        self.marks.get(index).copied().flatten().unwrap_or(colors.default)
+    }
+
     pub fn frame(&mut self) -> &Rc<RefCell<frame_types::Text>>{
         &self.frame
     }
@@ -141,7 +160,7 @@ impl ListUi {
         if frame.len() == 0 {return}
 
         for i in 0..frame.len(){
-            frame.set_fg(i, colors.default);
+            frame.set_fg(i, self.row_color(i, colors));
             frame.set_bg(i, colors.background);
         }
 
@@ -165,6 +184,7 @@ impl ListUi {
 
     pub fn clear(&mut self) {
         self.frame.borrow_mut().clear();
+        self.marks.clear();
     }
 
     pub fn update(&self, manager: &mut Manager) {
@@ -173,5 +193,6 @@ impl ListUi {
 
     pub fn truncate(&mut self, len: usize) {
         self.frame.borrow_mut().truncate(len);
+        self.marks.truncate(len);
     }
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
 use std::env::args;
 use std::path::PathBuf;
-use cli::Todo;
+use cli::{Todo, run_command};
 
 struct Arg {
     reset: bool,
@@ -13,27 +13,26 @@ fn handle_args() -> Arg {
         file: None,
     };
 
-    for arg in args() {
+    for arg in args().skip(1) {
         if arg == "-r" {
             out.reset = true;
             continue
         }
 
-        // let path = PathBuf::from(arg);
-
-        // if path.is_file() {
-        //     out.file = Some(path);
-        //     continue
-        // }
+        //the list is created when it is saved if the file does not exist yet.
+        out.file = Some(PathBuf::from(arg));
     }
 
     out
 }
 
 fn main(){
-    let arg = handle_args();
+    let args: Vec<String> = args().skip(1).collect();
+    if let Some(code) = run_command(&args) {
+        std::process::exit(code);
+    }
 
-    
+    let arg = handle_args();
 
     let mut todo = Todo::new(arg.reset, arg.file).unwrap();
     todo.main();
@@ -0,0 +1,99 @@
+use frames::*;
+
+use super::*;
+
+pub enum NoteControls {
+    Esc,
+    PointerUp,
+    PointerDown,
+    Select,
+    Delete,
+}
+
+///Shows the notes of an entry one line per row. The last row adds a new line.
+pub struct NoteEditor {
+    ui: ListUi,
+    lines: Vec<String>,
+}
+
+impl NoteEditor {
+    pub fn new(manager: &mut Manager, colors: &Colors) -> NoteEditor {
+        let mut ui = ListUi::new(manager, Some(Box::new(MainUiUpdate{})), colors);
+        ui.disable();
+
+        NoteEditor{
+            ui: ui,
+            lines: Vec::new(),
+        }
+    }
+
+    ///Edits the notes of the entry with the given name until the user exits. Returns the new notes.
+    pub fn main(&mut self, name: &str, notes: &str, settings: &Settings, manager: &mut Manager, prompt: &mut Prompt) -> String {
+        use NoteControls::*;
+
+        self.lines = notes.lines().map(String::from).collect();
+        self.ui.set_pointer_no_update(0);
+        self.ui.enable();
+        self.write_lines(settings, manager);
+
+        loop {
+            prompt.set_prompt(manager, format!("Notes of \"{}\"\n{}", name, settings.controls().notes_prompt()));
+            manager.draw().unwrap();
+
+            match settings.controls().notes(manager) {
+                Esc         => break,
+                PointerUp   => self.ui.dec_pointer(manager, &settings.colors(), 1),
+                PointerDown => self.ui.inc_pointer(manager, &settings.colors(), 1),
+                Select      => self.select(settings, manager, prompt),
+                Delete      => self.delete(settings, manager),
+            }
+        }
+
+        self.ui.disable();
+        self.lines.join("\n")
+    }
+
+    fn write_lines(&mut self, settings: &Settings, manager: &mut Manager) {
+        self.ui.clear();
+
+        for line in &self.lines {
+            self.ui.push(line.clone());
+        }
+        self.ui.push(String::from("<New Line>"));
+
+        if self.ui.pointer() >= self.ui.len() {
+            self.ui.set_pointer_no_update(self.ui.len() - 1);
+        }
+
+        self.ui.refresh_colors(&settings.colors(), manager);
+    }
+
+    fn select(&mut self, settings: &Settings, manager: &mut Manager, prompt: &mut Prompt) {
+        let index = self.ui.pointer();
+
+        if index < self.lines.len() {
+            if let Some(line) = prompt.edit_string(manager, String::from("New text of line."), &self.lines[index]) {
+                self.lines[index] = line;
+            }
+        }
+        else {
+            let line = match prompt.get_string(manager, String::from("Text of new line.")) {
+                Some(line) if !line.is_empty() => line,
+                _ => return,
+            };
+
+            self.lines.push(line);
+            self.ui.set_pointer_no_update(self.lines.len());
+        }
+
+        self.write_lines(settings, manager);
+    }
+
+    fn delete(&mut self, settings: &Settings, manager: &mut Manager) {
+        let index = self.ui.pointer();
+        if index >= self.lines.len() { return }
+
+        self.lines.remove(index);
+        self.write_lines(settings, manager);
+    }
+}
@@ -1,9 +1,10 @@
-use std::io::{stdout, stdin};
+use std::io::stdout;
 
 use frames::*;
 use super::Colors;
 
-use crossterm::{terminal, ExecutableCommand};
+use crossterm::{cursor, ExecutableCommand};
+use crossterm::event::{read, Event, KeyCode, KeyEvent, KeyModifiers};
 
 //use super::control::*;
 //use super::display::Display;
@@ -21,6 +22,8 @@ impl SizeUpdate for PromptUpdate {
 pub struct Prompt{
     object: Rc<RefCell<Object>>,
     frame:  Rc<RefCell<frame_types::Text>>,
+    ///Key that cancels the input, kept in line with the escape control.
+    escape: KeyCode,
 }
 
 impl Prompt {
@@ -36,9 +39,14 @@ impl Prompt {
         Prompt{
             object: obj,
             frame: frame,
+            escape: KeyCode::Esc,
         }
     }
 
+    pub fn set_escape(&mut self, escape: KeyCode) {
+        self.escape = escape;
+    }
+
     pub fn refresh_colors(&mut self, colors: &Colors, manager: &mut Manager){
         let mut frame = self.frame.borrow_mut();
         frame.fill = PixelData::new(' ', colors.default, colors.background);
@@ -61,26 +69,170 @@ impl Prompt {
         manager.add_task(self.object.borrow().update());
     }
 
-    pub fn get_string(&mut self, manager: &mut Manager, prompt: String) -> String {
-        String::from(self.get_string_no_trim(manager, prompt).trim())
+    ///Returns None if the input was cancelled.
+    pub fn get_string(&mut self, manager: &mut Manager, prompt: String) -> Option<String> {
+        self.get_string_no_trim(manager, prompt).map(|text| String::from(text.trim()))
+    }
+
+    ///Asks for a replacement of the given text, starting with the text already typed in. Entering nothing keeps the current text.
+    pub fn edit_string(&mut self, manager: &mut Manager, prompt: String, current: &str) -> Option<String> {
+        let new = self.read_line(manager, prompt, current)?;
+        let new = new.trim();
+
+        if new.is_empty() {
+            Some(String::from(current))
+        }
+        else {
+            Some(String::from(new))
+        }
+    }
+
+    pub fn get_string_no_trim(&mut self, manager: &mut Manager, prompt: String) -> Option<String> {
+        self.read_line(manager, prompt, "")
+    }
+
+    ///Edits a line of text below the prompt until enter or escape is pressed. Returns None on escape.
+    fn read_line(&mut self, manager: &mut Manager, prompt: String, initial: &str) -> Option<String> {
+        let mut line = LineEditor::new(initial);
+
+        loop {
+            self.draw_line(manager, &prompt, &mut line);
+
+            match read().unwrap() {
+                Event::Mouse(_) => (),
+                Event::Resize(x, y) => { manager.resize(x, y); },
+                Event::Key(KeyEvent{code, modifiers, ..}) => {
+                    let control = modifiers.contains(KeyModifiers::CONTROL);
+
+                    match (code, control) {
+                        (KeyCode::Enter, _)                                  => return Some(line.text()),
+                        (x, _) if cancels(x, self.escape)                    => return None,
+                        (KeyCode::Left, false)                               => line.left(),
+                        (KeyCode::Right, false)                              => line.right(),
+                        (KeyCode::Left, true)                                => line.cursor = line.word_start(),
+                        (KeyCode::Right, true)                               => line.cursor = line.word_end(),
+                        (KeyCode::Home, _) | (KeyCode::Char('a'), true)      => line.cursor = 0,
+                        (KeyCode::End, _)  | (KeyCode::Char('e'), true)      => line.cursor = line.text.len(),
+                        (KeyCode::Backspace, true) | (KeyCode::Char('w'), true) => line.delete_word(),
+                        (KeyCode::Backspace, false)                          => line.backspace(),
+                        (KeyCode::Delete, _)                                 => line.delete(),
+                        (KeyCode::Char(c), false)                            => line.insert(c),
+                        _ => (),
+                    }
+                }
+            }
+        }
     }
 
-    pub fn get_string_no_trim(&mut self, manager: &mut Manager, mut prompt: String) -> String {
-        prompt.push_str("\n: ");
-        self.set_prompt(manager, prompt);
+    ///Shows the prompt with the part of the line around the cursor after it and moves the terminal cursor into place.
+    fn draw_line(&mut self, manager: &mut Manager, prompt: &str, line: &mut LineEditor) {
+        //room is left after the text for the cursor.
+        let width = (manager.get_size().x as usize).saturating_sub(INPUT_START + 1).max(1);
+        line.scroll_to_cursor(width);
+
+        self.set_prompt(manager, format!("{}\n: {}", prompt, line.visible(width)));
         manager.draw().unwrap();
 
-        let Coord{x, y} = self.object.borrow().pos + Coord{x: 2, y: 1};
+        let Coord{x, y} = self.object.borrow().pos;
+        let row = prompt.matches('\n').count() + 1;
+        let column = INPUT_START + line.cursor - line.scroll;
 
-        terminal::disable_raw_mode().unwrap();
-        stdout().execute(crossterm::cursor::MoveTo(x as u16, y as u16)).unwrap();
+        stdout().execute(cursor::MoveTo(x as u16 + column as u16, y as u16 + row as u16)).unwrap();
+    }
+}
+
+///Esc always cancels input. The escape control does too, unless it is a character that has to be typed.
+pub fn cancels(code: KeyCode, escape: KeyCode) -> bool {
+    code == KeyCode::Esc || (code == escape && !matches!(escape, KeyCode::Char(_)))
+}
+
+///Columns taken by ": " before the input.
+const INPUT_START: usize = 2;
+
+///A line of text being typed with the cursor and how far the line is scrolled, both counted in characters.
+struct LineEditor {
+    text: Vec<char>,
+    cursor: usize,
+    scroll: usize,
+}
+
+impl LineEditor {
+    fn new(initial: &str) -> LineEditor {
+        let text: Vec<char> = initial.chars().collect();
 
-        let mut text = String::new();
-        stdin().read_line(&mut text).unwrap();
+        LineEditor{
+            cursor: text.len(),
+            text: text,
+            scroll: 0,
+        }
+    }
+
+    fn text(&self) -> String {
+        self.text.iter().collect()
+    }
 
-        terminal::enable_raw_mode().unwrap();
+    fn insert(&mut self, c: char) {
+        self.text.insert(self.cursor, c);
+        self.cursor += 1;
+    }
+
+    fn left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
 
-        String::from(text)
+    fn right(&mut self) {
+        self.cursor = (self.cursor + 1).min(self.text.len());
+    }
+
+    fn backspace(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            self.text.remove(self.cursor);
+        }
+    }
+
+    fn delete(&mut self) {
+        if self.cursor < self.text.len() {
+            self.text.remove(self.cursor);
+        }
+    }
+
+    ///Removes the word before the cursor along with the spaces after it.
+    fn delete_word(&mut self) {
+        let start = self.word_start();
+        self.text.drain(start..self.cursor);
+        self.cursor = start;
+    }
+
+    ///Start of the word before the cursor.
+    fn word_start(&self) -> usize {
+        let mut i = self.cursor;
+        while i > 0 && self.text[i - 1].is_whitespace() { i -= 1 }
+        while i > 0 && !self.text[i - 1].is_whitespace() { i -= 1 }
+        i
+    }
+
+    ///End of the word after the cursor.
+    fn word_end(&self) -> usize {
+        let mut i = self.cursor;
+        while i < self.text.len() && self.text[i].is_whitespace() { i += 1 }
+        while i < self.text.len() && !self.text[i].is_whitespace() { i += 1 }
+        i
+    }
+
+    ///Scrolls just far enough for the cursor to be in view.
+    fn scroll_to_cursor(&mut self, width: usize) {
+        if self.cursor < self.scroll {
+            self.scroll = self.cursor;
+        }
+        else if self.cursor >= self.scroll + width {
+            self.scroll = self.cursor + 1 - width;
+        }
+    }
+
+    fn visible(&self, width: usize) -> String {
+        let end = (self.scroll + width).min(self.text.len());
+        self.text[self.scroll..end].iter().collect()
     }
 }
 
@@ -140,4 +292,58 @@ pub fn config_controls(controls: &ControlSettings) -> String{
     controls.key_at_control(Control::Config).display(),
     }
 }
-*/
\ No newline at end of file
+*/
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_start() {
+        let mut line = LineEditor::new("one two  three");
+        assert_eq!(line.word_start(), 9);
+
+        line.cursor = 9;
+        assert_eq!(line.word_start(), 4);
+
+        line.cursor = 2;
+        assert_eq!(line.word_start(), 0);
+    }
+
+    #[test]
+    fn delete_word() {
+        let mut line = LineEditor::new("one two ");
+        line.delete_word();
+        assert_eq!(line.text(), "one ");
+        assert_eq!(line.cursor, 4);
+
+        line.delete_word();
+        assert_eq!(line.text(), "");
+        assert_eq!(line.cursor, 0);
+    }
+
+    #[test]
+    fn scroll() {
+        let mut line = LineEditor::new("abcdefghij");
+        line.scroll_to_cursor(4);
+        assert_eq!(line.scroll, 7);
+        assert_eq!(line.visible(4), "hij");
+
+        line.cursor = 5;
+        line.scroll_to_cursor(4);
+        assert_eq!(line.scroll, 5);
+        assert_eq!(line.visible(4), "fghi");
+
+        line.cursor = 0;
+        line.scroll_to_cursor(4);
+        assert_eq!(line.scroll, 0);
+        assert_eq!(line.visible(4), "abcd");
+    }
+
+    #[test]
+    fn cancel_keys() {
+        assert!(cancels(KeyCode::Esc, KeyCode::Char('q')));
+        assert!(cancels(KeyCode::Tab, KeyCode::Tab));
+        assert!(!cancels(KeyCode::Char('q'), KeyCode::Char('q')));
+        assert!(!cancels(KeyCode::Tab, KeyCode::Esc));
+    }
+}
@@ -12,6 +12,10 @@ pub struct Colors{
     pub background: Color,
     pub pointer:    Color,
     pub selected:   Color,
+    pub overdue:    Color,
+    pub due_today:  Color,
+    pub search:     Color,
+    pub partial:    Color,
 }
 
 impl Colors{
@@ -21,6 +25,10 @@ impl Colors{
             background: Color::Rgb{r:   0, g:   0, b:   0},
             pointer:    Color::Rgb{r:   0, g: 255, b: 255},
             selected:   Color::Rgb{r: 255, g:   0, b: 255},
+            overdue:    Color::Rgb{r: 255, g:  64, b:  64},
+            due_today:  Color::Rgb{r: 255, g: 200, b:   0},
+            search:     Color::Rgb{r:   0, g: 255, b:   0},
+            partial:    Color::Rgb{r: 128, g: 160, b: 255},
         }
     }
 
@@ -32,6 +40,10 @@ impl Colors{
             writer.named_value(b"background", &self.background)?;
             writer.named_value(b"pointer",    &self.pointer)?;
             writer.named_value(b"selected",   &self.selected)?;
+            writer.named_value(b"overdue",    &self.overdue)?;
+            writer.named_value(b"due_today",  &self.due_today)?;
+            writer.named_value(b"search",     &self.search)?;
+            writer.named_value(b"partial",    &self.partial)?;
 
         writer.write_event(Event::End(name.to_end()))?;
         Ok(())
@@ -43,7 +55,11 @@ impl Colors{
             {self.default, b"default"},
             {self.background, b"background"},
             {self.pointer, b"pointer"},
-            {self.selected, b"selected"}
+            {self.selected, b"selected"},
+            {self.overdue, b"overdue"},
+            {self.due_today, b"due_today"},
+            {self.search, b"search"},
+            {self.partial, b"partial"}
         );
 
         Ok(())
@@ -55,6 +71,10 @@ impl Colors{
             1  => Some(&mut self.background),
             2  => Some(&mut self.pointer),
             3  => Some(&mut self.selected),
+            4  => Some(&mut self.overdue),
+            5  => Some(&mut self.due_today),
+            6  => Some(&mut self.search),
+            7  => Some(&mut self.partial),
             _ => None
         }
     }
@@ -22,6 +22,32 @@ pub struct Controls {
     pub delete:             KeyCode,
     pub copy:               KeyCode,
     pub move_entry:         KeyCode,
+    pub focus:              KeyCode,
+    pub unfocus:            KeyCode,
+    pub edit:               KeyCode,
+    pub undo:               KeyCode,
+    pub redo:               KeyCode,
+    pub save_as:            KeyCode,
+    pub export_markdown:    KeyCode,
+    pub export_json:        KeyCode,
+    pub notes:              KeyCode,
+    pub due:                KeyCode,
+    pub overdue:            KeyCode,
+    pub priority:           KeyCode,
+    pub sort:               KeyCode,
+    pub filter:             KeyCode,
+    pub search:             KeyCode,
+    pub hide_completed:     KeyCode,
+    pub fold:               KeyCode,
+    pub fold_all:           KeyCode,
+    pub expand_all:         KeyCode,
+    pub insert_above:       KeyCode,
+    pub insert_below:       KeyCode,
+    pub insert_first_child: KeyCode,
+    pub move_up:            KeyCode,
+    pub move_down:          KeyCode,
+    pub indent:             KeyCode,
+    pub outdent:            KeyCode,
 }
 
 impl Controls {
@@ -41,6 +67,32 @@ impl Controls {
             delete:             KeyCode::Delete,
             copy:               KeyCode::Char('c'),
             move_entry:         KeyCode::Char('m'),
+            focus:              KeyCode::Char('f'),
+            unfocus:            KeyCode::Char('b'),
+            edit:               KeyCode::Char('e'),
+            undo:               KeyCode::Char('u'),
+            redo:               KeyCode::Char('r'),
+            save_as:            KeyCode::Char('a'),
+            export_markdown:    KeyCode::Char('k'),
+            export_json:        KeyCode::Char('j'),
+            notes:              KeyCode::Char('n'),
+            due:                KeyCode::Char('d'),
+            overdue:            KeyCode::Char('l'),
+            priority:           KeyCode::Char('i'),
+            sort:               KeyCode::Char('t'),
+            filter:             KeyCode::Char('#'),
+            search:             KeyCode::Char('/'),
+            hide_completed:     KeyCode::Char('x'),
+            fold:               KeyCode::Char('z'),
+            fold_all:           KeyCode::Char('-'),
+            expand_all:         KeyCode::Char('+'),
+            insert_above:       KeyCode::Char('A'),
+            insert_below:       KeyCode::Char('B'),
+            insert_first_child: KeyCode::Char('C'),
+            move_up:            KeyCode::Char('K'),
+            move_down:          KeyCode::Char('J'),
+            indent:             KeyCode::Tab,
+            outdent:            KeyCode::BackTab,
         }
     }
 
@@ -97,6 +149,32 @@ impl Controls {
                 x if x == self.delete           => {return Delete}
                 x if x == self.copy             => {return Copy}
                 x if x == self.move_entry       => {return Move}
+                x if x == self.focus            => {return Focus}
+                x if x == self.unfocus          => {return Unfocus}
+                x if x == self.edit             => {return Edit}
+                x if x == self.undo             => {return Undo}
+                x if x == self.redo             => {return Redo}
+                x if x == self.save_as          => {return SaveAs}
+                x if x == self.export_markdown  => {return ExportMarkdown}
+                x if x == self.export_json      => {return ExportJson}
+                x if x == self.notes            => {return Notes}
+                x if x == self.due              => {return SetDue}
+                x if x == self.overdue          => {return Overdue}
+                x if x == self.priority         => {return Priority}
+                x if x == self.sort             => {return Sort}
+                x if x == self.filter           => {return Filter}
+                x if x == self.search           => {return Search}
+                x if x == self.hide_completed   => {return HideCompleted}
+                x if x == self.fold             => {return ToggleFold}
+                x if x == self.fold_all         => {return FoldAll}
+                x if x == self.expand_all       => {return ExpandAll}
+                x if x == self.insert_above     => {return InsertAbove}
+                x if x == self.insert_below     => {return InsertBelow}
+                x if x == self.insert_first_child => {return InsertFirstChild}
+                x if x == self.move_up          => {return MoveUp}
+                x if x == self.move_down        => {return MoveDown}
+                x if x == self.indent           => {return Indent}
+                x if x == self.outdent          => {return Outdent}
                 _ => (),
             }
         }
@@ -114,6 +192,77 @@ impl Controls {
         )
     }
 
+    pub fn unsaved(&self, manager: &mut Manager) -> Unsaved {
+        use Unsaved::*;
+
+        loop {
+            match Controls::get_event(manager) {
+                x if x == self.save   => {return Save}
+                x if x == self.delete => {return Discard}
+                x if x == self.escape => {return Cancel}
+                _ => (),
+            }
+        }
+    }
+
+    pub fn unsaved_prompt(&self) -> String {
+        format!("The list has unsaved changes.\nSave: \"{}\", Discard: \"{}\", Cancel: \"{}\"", 
+            self.save.display(),
+            self.delete.display(),
+            self.escape.display(),
+        )
+    }
+
+    pub fn notes(&self, manager: &mut Manager) -> NoteControls {
+        use NoteControls::*;
+
+        loop {
+            match Controls::get_event(manager) {
+                x if x == self.escape => {return Esc}
+                x if x == self.up     => {return PointerUp}
+                x if x == self.down   => {return PointerDown}
+                x if x == self.select => {return Select}
+                x if x == self.delete => {return Delete}
+                _ => (),
+            }
+        }
+    }
+
+    pub fn notes_prompt(&self) -> String {
+        format!("Exit: \"{}\", Up: \"{}\", Down: \"{}\", Edit Line: \"{}\", Delete Line: \"{}\"", 
+            self.escape.display(),
+            self.up.display(),
+            self.down.display(),
+            self.select.display(),
+            self.delete.display(),
+        )
+    }
+
+    pub fn search_mode(&self, manager: &mut Manager) -> SearchControls {
+        use SearchControls::*;
+
+        loop {
+            match Controls::get_event(manager) {
+                x if x == self.escape => {return Esc}
+                x if x == self.select => {return Esc}
+                x if x == self.up     => {return Previous}
+                x if x == self.down   => {return Next}
+                x if x == self.search => {return NewSearch}
+                _ => (),
+            }
+        }
+    }
+
+    pub fn search_prompt(&self) -> String {
+        format!("Done: \"{}\" or \"{}\", Previous: \"{}\", Next: \"{}\", New Search: \"{}\"", 
+            self.escape.display(),
+            self.select.display(),
+            self.up.display(),
+            self.down.display(),
+            self.search.display(),
+        )
+    }
+
     pub fn help(&self, manager: &mut Manager) -> HelpControls {
         use HelpControls::*;
 
@@ -210,6 +359,32 @@ impl Controls {
             writer.named_value(b"delete",           &self.delete)?;
             writer.named_value(b"copy",             &self.copy)?;
             writer.named_value(b"move_entry",       &self.move_entry)?;
+            writer.named_value(b"focus",            &self.focus)?;
+            writer.named_value(b"unfocus",          &self.unfocus)?;
+            writer.named_value(b"edit",             &self.edit)?;
+            writer.named_value(b"undo",             &self.undo)?;
+            writer.named_value(b"redo",             &self.redo)?;
+            writer.named_value(b"save_as",          &self.save_as)?;
+            writer.named_value(b"export_markdown",  &self.export_markdown)?;
+            writer.named_value(b"export_json",      &self.export_json)?;
+            writer.named_value(b"notes",            &self.notes)?;
+            writer.named_value(b"due",              &self.due)?;
+            writer.named_value(b"overdue",          &self.overdue)?;
+            writer.named_value(b"priority",         &self.priority)?;
+            writer.named_value(b"sort",             &self.sort)?;
+            writer.named_value(b"filter",           &self.filter)?;
+            writer.named_value(b"search",           &self.search)?;
+            writer.named_value(b"hide_completed",   &self.hide_completed)?;
+            writer.named_value(b"fold",             &self.fold)?;
+            writer.named_value(b"fold_all",         &self.fold_all)?;
+            writer.named_value(b"expand_all",       &self.expand_all)?;
+            writer.named_value(b"insert_above",     &self.insert_above)?;
+            writer.named_value(b"insert_below",     &self.insert_below)?;
+            writer.named_value(b"insert_first_child", &self.insert_first_child)?;
+            writer.named_value(b"move_up",          &self.move_up)?;
+            writer.named_value(b"move_down",        &self.move_down)?;
+            writer.named_value(b"indent",           &self.indent)?;
+            writer.named_value(b"outdent",          &self.outdent)?;
 
         writer.write_event(Event::End(name.to_end()))?;
         Ok(())
@@ -231,7 +406,33 @@ impl Controls {
             {self.print_unfinished, b"print_unfinished"},
             {self.delete, b"delete"},
             {self.copy, b"copy"},
-            {self.move_entry, b"move_entry"}
+            {self.move_entry, b"move_entry"},
+            {self.focus, b"focus"},
+            {self.unfocus, b"unfocus"},
+            {self.edit, b"edit"},
+            {self.undo, b"undo"},
+            {self.redo, b"redo"},
+            {self.save_as, b"save_as"},
+            {self.export_markdown, b"export_markdown"},
+            {self.export_json, b"export_json"},
+            {self.notes, b"notes"},
+            {self.due, b"due"},
+            {self.overdue, b"overdue"},
+            {self.priority, b"priority"},
+            {self.sort, b"sort"},
+            {self.filter, b"filter"},
+            {self.search, b"search"},
+            {self.hide_completed, b"hide_completed"},
+            {self.fold, b"fold"},
+            {self.fold_all, b"fold_all"},
+            {self.expand_all, b"expand_all"},
+            {self.insert_above, b"insert_above"},
+            {self.insert_below, b"insert_below"},
+            {self.insert_first_child, b"insert_first_child"},
+            {self.move_up, b"move_up"},
+            {self.move_down, b"move_down"},
+            {self.indent, b"indent"},
+            {self.outdent, b"outdent"}
         );
 
         Ok(())
@@ -253,6 +454,32 @@ impl Controls {
             11 => Some(&mut self.delete),
             12 => Some(&mut self.copy),
             13 => Some(&mut self.move_entry),
+            14 => Some(&mut self.focus),
+            15 => Some(&mut self.unfocus),
+            16 => Some(&mut self.edit),
+            17 => Some(&mut self.undo),
+            18 => Some(&mut self.redo),
+            19 => Some(&mut self.save_as),
+            20 => Some(&mut self.export_markdown),
+            21 => Some(&mut self.export_json),
+            22 => Some(&mut self.notes),
+            23 => Some(&mut self.due),
+            24 => Some(&mut self.overdue),
+            25 => Some(&mut self.priority),
+            26 => Some(&mut self.sort),
+            27 => Some(&mut self.filter),
+            28 => Some(&mut self.search),
+            29 => Some(&mut self.hide_completed),
+            30 => Some(&mut self.fold),
+            31 => Some(&mut self.fold_all),
+            32 => Some(&mut self.expand_all),
+            33 => Some(&mut self.insert_above),
+            34 => Some(&mut self.insert_below),
+            35 => Some(&mut self.insert_first_child),
+            36 => Some(&mut self.move_up),
+            37 => Some(&mut self.move_down),
+            38 => Some(&mut self.indent),
+            39 => Some(&mut self.outdent),
             _ => None
         }
     }
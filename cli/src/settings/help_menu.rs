@@ -144,6 +144,32 @@ impl HelpMenu {
         frame.push(format!("Print into .txt            : {}", controls.print.display_quot()));
         frame.push(format!("Print unfinished into .txt : {}", controls.print_unfinished.display_quot()));
         frame.push(format!("Delete                     : {}", controls.delete.display_quot()));
+        frame.push(format!("Focus Selected             : {}", controls.focus.display_quot()));
+        frame.push(format!("Return Focus               : {}", controls.unfocus.display_quot()));
+        frame.push(format!("Edit Selected              : {}", controls.edit.display_quot()));
+        frame.push(format!("Undo                       : {}", controls.undo.display_quot()));
+        frame.push(format!("Redo                       : {}", controls.redo.display_quot()));
+        frame.push(format!("Save As                    : {}", controls.save_as.display_quot()));
+        frame.push(format!("Export into .md            : {}", controls.export_markdown.display_quot()));
+        frame.push(format!("Export into .json          : {}", controls.export_json.display_quot()));
+        frame.push(format!("Edit Notes                 : {}", controls.notes.display_quot()));
+        frame.push(format!("Set Due Date               : {}", controls.due.display_quot()));
+        frame.push(format!("List Overdue               : {}", controls.overdue.display_quot()));
+        frame.push(format!("Cycle Priority             : {}", controls.priority.display_quot()));
+        frame.push(format!("Sort Children              : {}", controls.sort.display_quot()));
+        frame.push(format!("Filter by Tag              : {}", controls.filter.display_quot()));
+        frame.push(format!("Search                     : {}", controls.search.display_quot()));
+        frame.push(format!("Hide Completed             : {}", controls.hide_completed.display_quot()));
+        frame.push(format!("Fold or Unfold Entry       : {}", controls.fold.display_quot()));
+        frame.push(format!("Fold All                   : {}", controls.fold_all.display_quot()));
+        frame.push(format!("Expand All                 : {}", controls.expand_all.display_quot()));
+        frame.push(format!("Insert Above               : {}", controls.insert_above.display_quot()));
+        frame.push(format!("Insert Below               : {}", controls.insert_below.display_quot()));
+        frame.push(format!("Insert First Child         : {}", controls.insert_first_child.display_quot()));
+        frame.push(format!("Move Entry Up              : {}", controls.move_up.display_quot()));
+        frame.push(format!("Move Entry Down            : {}", controls.move_down.display_quot()));
+        frame.push(format!("Indent Entry               : {}", controls.indent.display_quot()));
+        frame.push(format!("Outdent Entry              : {}", controls.outdent.display_quot()));
     }
 
     fn settings_menu(&mut self, controls: &Controls){
@@ -1,15 +1,21 @@
 use frames::{Manager, crossterm::style::Color};
 use super::*;
 use crate::*;
-use core::{Formatter, Entry, Format, Type};
+use core::{Formatter, Entry, Format, Type, Progress, ProgressStyle, ProgressCount, State};
 
 use MainControls::*;
 
+///Number of colors that can be changed in the color menu.
+const COLOR_COUNT: usize = 8;
+///The number of settings in the formatter menu before the examples.
+const FORMATTER_FIELDS: usize = 7;
+
 pub struct SettingMenu {
     main: ListUi,
     controls: ListUi,
     colors: ListUi,
     formatter: ListUi,
+    options: ListUi,
 }
 
 impl SettingMenu {
@@ -19,6 +25,7 @@ impl SettingMenu {
         main.push(String::from("Colors"));
         main.push(String::from("Formatter"));
         main.push(String::from("Print Formatter"));
+        main.push(String::from("Options"));
         main.push(String::from("Apply Changes"));
 
         main.set_pointer(0, manager, colors);
@@ -29,6 +36,7 @@ impl SettingMenu {
             controls: SettingMenu::new_control_menu(manager, colors, &data.controls),
             colors: SettingMenu::new_color_menu(manager, colors),
             formatter: SettingMenu::new_formatter_menu(manager, colors),
+            options: SettingMenu::new_options_menu(manager, colors),
         }
     }
 
@@ -49,6 +57,7 @@ impl SettingMenu {
         let mut temp_controls = cur_data.controls.clone();
         let mut temp_formatter = cur_data.formatter.clone();
         let mut temp_print_formatter = cur_data.print_formatter.clone();
+        let mut temp_options = cur_data.options.clone();
 
         loop {
             manager.draw().unwrap();
@@ -71,14 +80,17 @@ impl SettingMenu {
                         1 => self.colors(manager, help, prompt, cur_data, &mut temp_colors),
                         2 => self.formatter(manager, help, prompt, cur_data, &mut temp_formatter),
                         3 => self.formatter(manager, help, prompt, cur_data, &mut temp_print_formatter),
-                        4 => {
+                        4 => self.options(manager, help, prompt, cur_data, &mut temp_options),
+                        5 => {
                             cur_data.colors = temp_colors.clone();
                             cur_data.controls = temp_controls.clone();
                             cur_data.formatter = temp_formatter.clone();
                             cur_data.print_formatter = temp_print_formatter.clone();
+                            cur_data.options = temp_options.clone();
 
                             self.refresh_colors(&cur_data.colors, manager);
                             prompt.refresh_colors(&cur_data.colors, manager);
+                            prompt.set_escape(cur_data.controls.escape);
                             help.refresh_colors(&cur_data.colors, manager);
 
                             changes = true;
@@ -99,9 +111,8 @@ impl SettingMenu {
         self.main.refresh_colors(colors, manager);
         self.controls.refresh_colors(colors, manager);
         self.colors.refresh_colors(colors, manager);
-        self.colors.set_fg(6, colors.pointer);
-        self.colors.set_fg(7, colors.selected);
         self.formatter.refresh_colors(colors, manager);
+        self.options.refresh_colors(colors, manager);
     }
 
     fn new_control_menu(manager: &mut Manager, colors: &Colors, controls: &Controls) -> ListUi {
@@ -122,6 +133,32 @@ impl SettingMenu {
         menu.push(format!("Delete           : {}", controls.delete.display_quot()));
         menu.push(format!("Copy             : {}", controls.copy.display_quot()));
         menu.push(format!("Move Entry       : {}", controls.move_entry.display_quot()));
+        menu.push(format!("Focus            : {}", controls.focus.display_quot()));
+        menu.push(format!("Unfocus          : {}", controls.unfocus.display_quot()));
+        menu.push(format!("Edit             : {}", controls.edit.display_quot()));
+        menu.push(format!("Undo             : {}", controls.undo.display_quot()));
+        menu.push(format!("Redo             : {}", controls.redo.display_quot()));
+        menu.push(format!("Save As          : {}", controls.save_as.display_quot()));
+        menu.push(format!("Export Markdown  : {}", controls.export_markdown.display_quot()));
+        menu.push(format!("Export Json      : {}", controls.export_json.display_quot()));
+        menu.push(format!("Notes            : {}", controls.notes.display_quot()));
+        menu.push(format!("Due Date         : {}", controls.due.display_quot()));
+        menu.push(format!("Overdue          : {}", controls.overdue.display_quot()));
+        menu.push(format!("Priority         : {}", controls.priority.display_quot()));
+        menu.push(format!("Sort             : {}", controls.sort.display_quot()));
+        menu.push(format!("Filter by Tag    : {}", controls.filter.display_quot()));
+        menu.push(format!("Search           : {}", controls.search.display_quot()));
+        menu.push(format!("Hide Completed   : {}", controls.hide_completed.display_quot()));
+        menu.push(format!("Fold             : {}", controls.fold.display_quot()));
+        menu.push(format!("Fold All         : {}", controls.fold_all.display_quot()));
+        menu.push(format!("Expand All       : {}", controls.expand_all.display_quot()));
+        menu.push(format!("Insert Above     : {}", controls.insert_above.display_quot()));
+        menu.push(format!("Insert Below     : {}", controls.insert_below.display_quot()));
+        menu.push(format!("First Child      : {}", controls.insert_first_child.display_quot()));
+        menu.push(format!("Move Up          : {}", controls.move_up.display_quot()));
+        menu.push(format!("Move Down        : {}", controls.move_down.display_quot()));
+        menu.push(format!("Indent           : {}", controls.indent.display_quot()));
+        menu.push(format!("Outdent          : {}", controls.outdent.display_quot()));
 
         menu.set_pointer(0, manager, colors);
         menu
@@ -183,13 +220,20 @@ impl SettingMenu {
         menu.push(format!("background : {}", colors.background.display()));
         menu.push(format!("pointer    : {}", colors.pointer.display()));
         menu.push(format!("selected   : {}", colors.selected.display()));
+        menu.push(format!("overdue    : {}", colors.overdue.display()));
+        menu.push(format!("due today  : {}", colors.due_today.display()));
+        menu.push(format!("search     : {}", colors.search.display()));
+        menu.push(format!("partial    : {}", colors.partial.display()));
 
         menu.push(String::from("\nExamples:"));
         menu.push(String::from("Default"));
         menu.push(String::from("Pointer"));
-        menu.set_fg(6, colors.pointer);
         menu.push(String::from("Selected"));
-        menu.set_fg(7, colors.selected);
+        menu.push(String::from("Overdue"));
+        menu.push(String::from("Due Today"));
+        menu.push(String::from("Search Match"));
+        menu.push(String::from("Partly Done"));
+        SettingMenu::colors_example(&mut menu, colors);
 
         menu.set_pointer(0, manager, colors);
         menu
@@ -213,14 +257,14 @@ impl SettingMenu {
                 Esc         => break,
                 PointerUp   => {
                     if self.colors.pointer() == 0 {
-                        self.colors.set_pointer(3, manager, &cur_data.colors);
+                        self.colors.set_pointer(COLOR_COUNT - 1, manager, &cur_data.colors);
                     }
                     else {
                         self.colors.dec_pointer(manager, &cur_data.colors, 1)
                     }
                 }
                 PointerDown => {
-                    if self.colors.pointer() == 3 {
+                    if self.colors.pointer() == COLOR_COUNT - 1 {
                         self.colors.set_pointer(0, manager, &cur_data.colors);
                     }
                     else {
@@ -245,18 +289,24 @@ impl SettingMenu {
 
     fn color_select(&mut self, manager: &mut Manager, prompt: &mut Prompt, cur_data: &mut SettingData, temp: &mut Colors) {
         let red = loop {
-            if let Ok(new) = prompt.get_string(manager, String::from("Enter new red value ( 0 - 255 )")).parse() {
-                break new
+            match prompt.get_string(manager, String::from("Enter new red value ( 0 - 255 )")).filter(|input| !input.is_empty()).map(|input| input.parse()) {
+                Some(Ok(new)) => break new,
+                Some(Err(_))  => (),
+                None          => return prompt.set_prompt(manager, cur_data.controls.help_prompt()),
             }
         };
         let green = loop {
-            if let Ok(new) = prompt.get_string(manager, String::from("Enter new green value ( 0 - 255 )")).parse() {
-                break new
+            match prompt.get_string(manager, String::from("Enter new green value ( 0 - 255 )")).filter(|input| !input.is_empty()).map(|input| input.parse()) {
+                Some(Ok(new)) => break new,
+                Some(Err(_))  => (),
+                None          => return prompt.set_prompt(manager, cur_data.controls.help_prompt()),
             }
         };
         let blue = loop {
-            if let Ok(new) = prompt.get_string(manager, String::from("Enter new blue value ( 0 - 255 )")).parse() {
-                break new
+            match prompt.get_string(manager, String::from("Enter new blue value ( 0 - 255 )")).filter(|input| !input.is_empty()).map(|input| input.parse()) {
+                Some(Ok(new)) => break new,
+                Some(Err(_))  => (),
+                None          => return prompt.set_prompt(manager, cur_data.controls.help_prompt()),
             }
         };
 
@@ -266,19 +316,20 @@ impl SettingMenu {
         *color = Color::Rgb{r: red, g: green, b: blue};
         
         change_control(&mut self.colors, i, &color.display());
-        self.colors_example(temp);
+        SettingMenu::colors_example(&mut self.colors, temp);
 
         self.colors.update(manager);
         prompt.set_prompt(manager, cur_data.controls.help_prompt());
     }
 
-    fn colors_example(&mut self, colors: &Colors) {
-        self.colors.set_fg(5, colors.default);
-        self.colors.set_bg(5, colors.background);
-        self.colors.set_fg(6, colors.pointer);
-        self.colors.set_bg(6, colors.background);
-        self.colors.set_fg(7, colors.selected);
-        self.colors.set_bg(7, colors.background);
+    ///The examples are listed after the colors and the examples heading.
+    fn colors_example(menu: &mut ListUi, colors: &Colors) {
+        let examples = [colors.default, colors.pointer, colors.selected, colors.overdue, colors.due_today, colors.search, colors.partial];
+
+        for (i, color) in examples.iter().enumerate() {
+            menu.mark(COLOR_COUNT + 1 + i, Some(*color));
+            menu.set_bg(COLOR_COUNT + 1 + i, colors.background);
+        }
     }
 
     fn new_formatter_menu(manager: &mut Manager, colors: &Colors) -> ListUi {
@@ -288,7 +339,10 @@ impl SettingMenu {
         menu.push(String::from("Indent     :"));
         menu.push(String::from("Completed  :"));
         menu.push(String::from("Incomplete :"));
+        menu.push(String::from("Partial    :"));
         menu.push(String::from("Type       :"));
+        menu.push(String::from("Progress   :"));
+        menu.push(String::from("Count      :"));
 
         menu.push(String::from("\nExample:"));
         menu.push(String::new());
@@ -301,17 +355,21 @@ impl SettingMenu {
     }
 
     fn formatter_example(&mut self, formatter: &Formatter) {
-        self.formatter.set_text(5, formatter.format(&Entry{name: String::from("Root"),          complete: false}, 0, true));
-        self.formatter.set_text(6, formatter.format(&Entry{name: String::from("Child 1"),       complete: true},  1, true));
-        self.formatter.set_text(7, formatter.format(&Entry{name: String::from("Child 1 child"), complete: true},  2, false));
-        self.formatter.set_text(8, formatter.format(&Entry{name: String::from("Child 2"),       complete: false}, 1, false));
+        let start = FORMATTER_FIELDS + 1;
+        self.formatter.set_text(start,     formatter.format(&Entry::new(String::from("Root"),          false), State::Partial,    0, true,  Some(Progress{done: 1, total: 2})));
+        self.formatter.set_text(start + 1, formatter.format(&Entry::new(String::from("Child 1"),       true),  State::Complete,   1, true,  Some(Progress{done: 1, total: 1})));
+        self.formatter.set_text(start + 2, formatter.format(&Entry::new(String::from("Child 1 child"), true),  State::Complete,   2, false, None));
+        self.formatter.set_text(start + 3, formatter.format(&Entry::new(String::from("Child 2"),       false), State::Incomplete, 1, false, None));
     }
 
     fn fill_formatter_menu(&mut self, temp: &mut Formatter) {
         change_control(&mut self.formatter, 0, &format!("{}", temp.get_indent()));
         change_control(&mut self.formatter, 1, &format!("\"{}\"", temp.get_completed()));
         change_control(&mut self.formatter, 2, &format!("\"{}\"", temp.get_incomplete()));
-        change_control(&mut self.formatter, 3, &temp.get_type().display_quot());
+        change_control(&mut self.formatter, 3, &format!("\"{}\"", temp.get_partial()));
+        change_control(&mut self.formatter, 4, &temp.get_type().display_quot());
+        change_control(&mut self.formatter, 5, &temp.get_progress().display_quot());
+        change_control(&mut self.formatter, 6, &temp.get_count().display_quot());
     }
 
     fn formatter(&mut self,
@@ -335,14 +393,14 @@ impl SettingMenu {
                 Esc         => break,
                 PointerUp   => {
                     if self.formatter.pointer() == 0 {
-                        self.formatter.set_pointer(3, manager, &cur_data.colors);
+                        self.formatter.set_pointer(FORMATTER_FIELDS - 1, manager, &cur_data.colors);
                     }
                     else {
                         self.formatter.dec_pointer(manager, &cur_data.colors, 1)
                     }
                 }
                 PointerDown => {
-                    if self.formatter.pointer() == 3 {
+                    if self.formatter.pointer() == FORMATTER_FIELDS - 1 {
                         self.formatter.set_pointer(0, manager, &cur_data.colors);
                     }
                     else {
@@ -368,41 +426,125 @@ impl SettingMenu {
     fn formatter_select(&mut self, manager: &mut Manager, prompt: &mut Prompt, cur_data: &mut SettingData, temp: &mut Formatter) {
         match self.formatter.pointer() {
             0 => {
-                if let Ok(num) = prompt.get_string(manager, String::from("Enter how deep the indent should be")).parse() {
-                    temp.set(Some(num), None, None, None);
+                if let Some(Ok(num)) = prompt.get_string(manager, String::from("Enter how deep the indent should be")).map(|input| input.parse()) {
+                    temp.set(Some(num), None, None, None, None);
                     change_control(&mut self.formatter, 0, &format!("{}", temp.get_indent()));
                     self.formatter_example(temp);
                 }
             }
             1 => {
-                if let Some(new) = prompt.get_string_no_trim(manager, String::from("Enter a new character to use when a task is complete")).chars().next() {
-                    temp.set(None, Some(new), None, None);
+                if let Some(new) = prompt.get_string_no_trim(manager, String::from("Enter a new character to use when a task is complete")).and_then(|input| input.chars().next()) {
+                    temp.set(None, Some(new), None, None, None);
                     change_control(&mut self.formatter, 1, &format!("\"{}\"", temp.get_completed()));
                     self.formatter_example(temp);
                 }
             }
             2 => {
-                if let Some(new) = prompt.get_string_no_trim(manager, String::from("Enter a new character to use when a task is incomplete complete")).chars().next() {
-                    temp.set(None, None, Some(new), None);
+                if let Some(new) = prompt.get_string_no_trim(manager, String::from("Enter a new character to use when a task is incomplete complete")).and_then(|input| input.chars().next()) {
+                    temp.set(None, None, Some(new), None, None);
                     change_control(&mut self.formatter, 2, &format!("\"{}\"", temp.get_incomplete()));
                     self.formatter_example(temp);
                 }
             }
             3 => {
+                if let Some(new) = prompt.get_string_no_trim(manager, String::from("Enter a new character to use when a task is partly complete")).and_then(|input| input.chars().next()) {
+                    temp.set(None, None, None, Some(new), None);
+                    change_control(&mut self.formatter, 3, &format!("\"{}\"", temp.get_partial()));
+                    self.formatter_example(temp);
+                }
+            }
+            4 => {
                 let new = match temp.get_type() {
                     Type::Basic => Type::Fancy,
                     Type::Fancy => Type::Basic,
                 };
-                temp.set(None, None, None, Some(new));
-                change_control(&mut self.formatter, 3, &temp.get_type().display_quot());
+                temp.set(None, None, None, None, Some(new));
+                change_control(&mut self.formatter, 4, &temp.get_type().display_quot());
                 self.formatter_example(temp);
             }
+            5 => {
+                let new = match temp.get_progress() {
+                    ProgressStyle::Off     => ProgressStyle::Count,
+                    ProgressStyle::Count   => ProgressStyle::Percent,
+                    ProgressStyle::Percent => ProgressStyle::Off,
+                };
+                temp.set_progress(Some(new), None);
+                change_control(&mut self.formatter, 5, &temp.get_progress().display_quot());
+                self.formatter_example(temp);
+            }
+            6 => {
+                let new = match temp.get_count() {
+                    ProgressCount::Leaves   => ProgressCount::Children,
+                    ProgressCount::Children => ProgressCount::Leaves,
+                };
+                temp.set_progress(None, Some(new));
+                change_control(&mut self.formatter, 6, &temp.get_count().display_quot());
+            }
             _ => ()
         }
 
         self.formatter.update(manager);
         prompt.set_prompt(manager, cur_data.controls.help_prompt());
     }
+
+    fn new_options_menu(manager: &mut Manager, colors: &Colors) -> ListUi {
+        let mut menu = ListUi::new(manager, Some(Box::new(MainUiUpdate{})), colors);
+        menu.disable();
+
+        menu.push(String::from("Backup on save :"));
+        menu.push(String::from("Autosave       :"));
+
+        menu.set_pointer(0, manager, colors);
+        menu
+    }
+
+    fn fill_options_menu(&mut self, temp: &mut Options) {
+        change_control(&mut self.options, 0, &temp.backup.display_quot());
+        change_control(&mut self.options, 1, &temp.autosave.display_quot());
+    }
+
+    fn options(&mut self,
+        manager: &mut Manager,
+        help:    &mut HelpMenu,
+        prompt:  &mut Prompt,
+        cur_data: &mut SettingData,
+        temp:     &mut Options) {
+
+        self.main.disable();
+        self.options.enable();
+        self.fill_options_menu(temp);
+        self.options.update(manager);
+
+        loop {
+            manager.draw().unwrap();
+
+            match cur_data.controls.main(manager){
+                Esc         => break,
+                PointerUp   => self.options.dec_pointer(manager, &cur_data.colors, 1),
+                PointerDown => self.options.inc_pointer(manager, &cur_data.colors, 1),
+                Help        => {
+                    self.options.disable();
+                    prompt.set_prompt(manager, cur_data.controls.help_prompt());
+                    help.main(manager, &cur_data.controls, HelpMenuType::Setting);
+                    self.options.enable();
+                    self.options.update(manager);
+                    prompt.set_prompt(manager, cur_data.controls.help_tip());
+                },
+                Select => {
+                    let i = self.options.pointer();
+
+                    let option = temp.index_field(i).unwrap();
+                    *option = !*option;
+
+                    change_control(&mut self.options, i, &option.display_quot());
+                    self.options.update(manager);
+                },
+            }
+        }
+
+        self.options.disable();
+        self.main.enable();
+    }
 }
 
 fn change_control(menu: &mut ListUi, index: usize, new: &str) {
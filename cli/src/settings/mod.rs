@@ -11,6 +11,9 @@ use controls::*;
 mod colors;
 pub use colors::*;
 
+mod options;
+pub use options::*;
+
 mod menu;
 
 mod settings;
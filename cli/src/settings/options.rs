@@ -0,0 +1,49 @@
+use std::io;
+use ez_quick_xml::{quick_xml, Reader, MoreWriter};
+use quick_xml::{Writer, events::*, de::from_str};
+
+use crate::try_load;
+
+#[derive(Copy, Clone)]
+pub struct Options{
+    pub backup: bool,
+    pub autosave: bool,
+}
+
+impl Options{
+    pub fn new() -> Options {
+        Options{
+            backup: true,
+            autosave: false,
+        }
+    }
+
+    pub fn save<W: io::Write>(&self, writer: &mut Writer<W>) -> quick_xml::Result<()> {
+        let name = BytesStart::borrowed_name(b"options");
+        writer.write_event(Event::Start(name.to_borrowed()))?;
+
+            writer.named_value(b"backup",   &self.backup)?;
+            writer.named_value(b"autosave", &self.autosave)?;
+
+        writer.write_event(Event::End(name.to_end()))?;
+        Ok(())
+    }
+
+    pub fn load<B: io::BufRead>(&mut self, reader: &mut Reader<B>) -> quick_xml::Result<()> {
+        try_load!(
+            reader,
+            {self.backup, b"backup"},
+            {self.autosave, b"autosave"}
+        );
+
+        Ok(())
+    }
+
+    pub fn index_field(&mut self, index: usize) -> Option<&mut bool> {
+        match index {
+            0  => Some(&mut self.backup),
+            1  => Some(&mut self.autosave),
+            _ => None
+        }
+    }
+}
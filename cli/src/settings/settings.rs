@@ -11,6 +11,7 @@ use quick_xml::{Writer, events::*, de::from_str};
 pub struct SettingData {
     pub controls: Controls,
     pub colors:   Colors,
+    pub options:  Options,
     pub formatter:       Formatter,
     pub print_formatter: Formatter,
 }
@@ -34,6 +35,7 @@ impl Settings {
         let data = SettingData {
             controls: Controls::default(),
             colors: colors,
+            options: Options::new(),
             formatter:       Formatter::default(),
             print_formatter: Formatter::default(),
         };
@@ -92,6 +94,10 @@ impl Settings {
         &self.data.colors
     }
 
+    pub fn options(&self) -> &Options {
+        &self.data.options
+    }
+
     ///Returns the formatter used for the drawing the list to the screen.
     pub fn formatter(&self) -> &Formatter{
         &self.data.formatter
@@ -127,6 +133,7 @@ impl Settings {
 
                 self.data.colors.save(&mut writer)?;
                 self.data.controls.save(&mut writer)?;
+                self.data.options.save(&mut writer)?;
                 Settings::save_formatter(&mut writer, &self.data.formatter, b"formatter")?;
                 Settings::save_formatter(&mut writer, &self.data.print_formatter, b"print_formatter")?;
 
@@ -142,8 +149,11 @@ impl Settings {
 
             writer.named_value(b"completed",  &formatter.get_completed())?;
             writer.named_value(b"incomplete", &formatter.get_incomplete())?;
+            writer.named_value(b"partial",    &formatter.get_partial())?;
             writer.named_value(b"indent",     &formatter.get_indent())?;
             writer.named_value(b"type",       &formatter.get_type())?;
+            writer.named_value(b"progress",   &formatter.get_progress())?;
+            writer.named_value(b"count",      &formatter.get_count())?;
 
         writer.write_event(Event::End(name.to_end()))?;
         Ok(())
@@ -158,6 +168,7 @@ impl Settings {
                     match reader.next()? {
                         Some(e) if e.name() == b"colors" =>          self.data.colors.load(&mut reader)?,
                         Some(e) if e.name() == b"controls" =>        self.data.controls.load(&mut reader)?,
+                        Some(e) if e.name() == b"options" =>         self.data.options.load(&mut reader)?,
                         Some(e) if e.name() == b"formatter" =>       Settings::load_formatter(&mut self.data.formatter, &mut reader)?,
                         Some(e) if e.name() == b"print_formatter" => Settings::load_formatter(&mut self.data.print_formatter, &mut reader)?,
                         Some(_) => (),
@@ -174,17 +185,24 @@ impl Settings {
         let mut indent = None;
         let mut completed = None;
         let mut incomplete = None;
+        let mut partial = None;
         let mut form_type = None;
+        let mut progress = None;
+        let mut count = None;
 
         try_load_formatter!(
             reader,
             {indent, b"indent"},
             {completed, b"completed"},
             {incomplete, b"incomplete"},
-            {form_type, b"form_type"}
+            {partial, b"partial"},
+            {form_type, b"form_type"},
+            {progress, b"progress"},
+            {count, b"count"}
         );
 
-        formatter.set(indent, completed, incomplete, form_type);
+        formatter.set(indent, completed, incomplete, partial, form_type);
+        formatter.set_progress(progress, count);
 
         Ok(())
     }
@@ -8,6 +8,7 @@ use crossterm::{terminal, ExecutableCommand, style};
 use terminal::{EnterAlternateScreen, LeaveAlternateScreen};
 use style::ResetColor;
 use ez_quick_xml::quick_xml;
+use core::FileKind;
 
 pub enum MainControls {
     Esc,
@@ -24,6 +25,10 @@ pub struct Todo {
     prompt: Prompt,
     settings: Settings,
     files: Vec<PathBuf>,
+    ///File given on the command line that is opened when the program starts.
+    file: Option<PathBuf>,
+    ///Shown in the prompt instead of the help for the selected item until the next key press.
+    message: Option<String>,
 }
 
 enum LeaveTo {
@@ -32,6 +37,7 @@ enum LeaveTo {
     LoadList,
     Help,
     Settings,
+    File(PathBuf),
 }
 
 impl Todo{
@@ -52,24 +58,29 @@ impl Todo{
         menu.push(String::from("Settings."));
 
         //if a file was given as an arg start with it open and move current dir to its parent.
-        let files = if let Some(file) = file {
+        //the full path is kept if that fails so the list is not opened in the wrong dir.
+        let file = file.and_then(|file| {
             if let Some(parent) = file.parent() {
-                let _ = std::env::set_current_dir(PathBuf::from(parent));
+                if !parent.as_os_str().is_empty() && std::env::set_current_dir(parent).is_err() {
+                    return Some(file)
+                }
             }
-            
-            vec![PathBuf::from(file.file_name().unwrap())]
-        }
-        else {
-            Vec::new()
-        };
+
+            file.file_name().map(PathBuf::from)
+        });
+
+        let mut prompt = Prompt::new(&mut manager, &settings.colors());
+        prompt.set_escape(settings.controls().escape);
 
         Ok(Todo{
             list: list,
             menu: menu,
-            prompt: Prompt::new(&mut manager, &settings.colors()),
+            prompt: prompt,
             manager: manager,
             settings: settings,
-            files: files,
+            files: Vec::new(),
+            file: file,
+            message: None,
         })
     }
 
@@ -81,18 +92,19 @@ impl Todo{
         terminal::enable_raw_mode().unwrap();
         self.manager.match_size().unwrap();
 
-        //if files is not empty then it means there was a provided file and it should be opened automatically.
-        if !self.files.is_empty() {
-            self.menu.set_pointer_no_update(4);
-            self.leave_to(LeaveTo::LoadList);
-        }
-        
         self.write_menu();
         self.menu.set_pointer(0, &mut self.manager, &self.settings.colors());
-        self.set_prompt_selected();
+
+        //a file given as an arg is opened automatically and closing it leaves the user in the file browser.
+        if let Some(file) = self.file.take() {
+            self.leave_to(LeaveTo::File(file));
+        }
         
         loop {
-            self.set_prompt_selected();
+            match self.message.take() {
+                Some(message) => self.set_prompt(&message),
+                None => self.set_prompt_selected(),
+            }
             self.manager.draw().unwrap();
 
             match self.settings.controls().main(&mut self.manager){
@@ -124,29 +136,29 @@ impl Todo{
 
         match place {
             ItemList => {
-                let mut data = {
-                    let name = self.get_string(String::from("Name of new list."));
-                    self.list.new_list(name)
-                };
-                
-                self.list.main(&mut data, &mut self.settings, &mut self.manager, &mut self.prompt);
+                if let Some(name) = self.get_string(String::from("Name of new list.")) {
+                    let data = self.list.new_list(name);
+
+                    self.open(data, "the new list");
+                }
             },
             ManLoadList => {
-                let data = {
-                    let file = PathBuf::from(self.get_string(String::from("List location.")));
-                    self.list.load_list(&file)
-                };
-                
-                if let Ok(mut data) = data {
-                    self.list.main(&mut data, &mut self.settings, &mut self.manager, &mut self.prompt);
+                if let Some(file) = self.get_string(String::from("List location.")).map(PathBuf::from) {
+                    let data = self.list.load_list(&file);
+
+                    self.open(data, &file.display().to_string());
                 }
             },
             LoadList => {
-                let data = self.load_selected_file();
+                let file = self.files[self.menu.pointer() - 4].clone();
+                let data = self.list.load_list(&file);
 
-                if let Ok(mut data) = data {
-                    self.list.main(&mut data, &mut self.settings, &mut self.manager, &mut self.prompt);
-                }
+                self.open(data, &file.display().to_string());
+            },
+            File(file) => {
+                let data = self.list.open_file(&file);
+
+                self.open(data, &file.display().to_string());
             },
             Help => {
                 self.settings.help_menu(&mut self.manager, &mut self.prompt, HelpMenuType::Main);
@@ -162,6 +174,14 @@ impl Todo{
         self.write_menu();
     }
 
+    ///Runs the list or keeps the reason it could not be opened to show in the prompt.
+    fn open(&mut self, data: Result<Data, core::Error>, name: &str) {
+        match data {
+            Ok(mut data) => self.list.main(&mut data, &mut self.settings, &mut self.manager, &mut self.prompt),
+            Err(err) => self.message = Some(format!("Could not open {}: {}", name, err)),
+        }
+    }
+
     fn select(&mut self){
 
         match self.menu.pointer() {
@@ -173,8 +193,9 @@ impl Todo{
         }
     }
 
-    fn get_string(&mut self, text: String) -> String {
-        self.prompt.get_string(&mut self.manager, text)
+    ///Returns None if the input was cancelled or left empty.
+    fn get_string(&mut self, text: String) -> Option<String> {
+        self.prompt.get_string(&mut self.manager, text).filter(|input| !input.is_empty())
     }
 
     fn set_prompt_selected(&mut self) {
@@ -205,9 +226,17 @@ impl Todo{
         for file in std::fs::read_dir(dir).unwrap(){
             if let Ok(file) = file{
                 let file = file.path();
-                if file.as_path().extension() == Some(std::ffi::OsStr::new("todo")) {
-                    self.menu.push(String::from(file.file_stem().unwrap().to_str().unwrap()));
-                    self.files.push(file);
+                match FileKind::of(&file) {
+                    Some(FileKind::Todo) => {
+                        self.menu.push(String::from(file.file_stem().unwrap().to_str().unwrap()));
+                        self.files.push(file);
+                    }
+                    //other formats show their extension so they are not mistaken for todo files.
+                    Some(_) => {
+                        self.menu.push(String::from(file.file_name().unwrap().to_str().unwrap()));
+                        self.files.push(file);
+                    }
+                    None => (),
                 }
             }
         }
@@ -216,14 +245,10 @@ impl Todo{
     }
 
     fn set_dir(&mut self) {
-        let input = self.prompt.get_string(&mut self.manager, String::from("Enter new Dir."));
-
-        if std::env::set_current_dir(PathBuf::from(input)).is_ok() {
-            self.write_menu();
+        if let Some(input) = self.get_string(String::from("Enter new Dir.")) {
+            if std::env::set_current_dir(PathBuf::from(input)).is_ok() {
+                self.write_menu();
+            }
         }
     }
-
-    fn load_selected_file(&mut self) -> Result<Data, bytebuffer::ByteErr> {
-        self.list.load_list(&self.files[self.menu.pointer() - 4])
-    }
 }
\ No newline at end of file
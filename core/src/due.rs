@@ -0,0 +1,116 @@
+use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime};
+use serde::{Serialize, Deserialize};
+
+use std::convert::TryFrom;
+use std::fmt;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const TIME_FORMAT: &str = "%H:%M";
+
+///When an entry has to be done by. Entries without a time are due at the end of the day.
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Due {
+    pub date: NaiveDate,
+    pub time: Option<NaiveTime>,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DueState {
+    Later,
+    Today,
+    Overdue,
+}
+
+impl Due {
+    ///Reads a date like "2024-05-01" with an optional time like "2024-05-01 17:30".
+    pub fn parse(text: &str) -> Option<Due> {
+        let mut parts = text.split_whitespace();
+
+        let date = NaiveDate::parse_from_str(parts.next()?, DATE_FORMAT).ok()?;
+        let time = match parts.next() {
+            Some(time) => Some(NaiveTime::parse_from_str(time, TIME_FORMAT).ok()?),
+            None => None,
+        };
+
+        if parts.next().is_some() { return None }
+
+        Some(Due{
+            date: date,
+            time: time,
+        })
+    }
+
+    pub fn state_at(&self, now: NaiveDateTime) -> DueState {
+        let today = now.date();
+
+        if self.date < today {
+            DueState::Overdue
+        }
+        else if self.date > today {
+            DueState::Later
+        }
+        else {
+            match self.time {
+                Some(time) if time < now.time() => DueState::Overdue,
+                _ => DueState::Today,
+            }
+        }
+    }
+
+    pub fn state(&self) -> DueState {
+        self.state_at(Local::now().naive_local())
+    }
+}
+
+impl fmt::Display for Due {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.date.format(DATE_FORMAT))?;
+
+        if let Some(time) = self.time {
+            write!(f, " {}", time.format(TIME_FORMAT))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl TryFrom<String> for Due {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        Due::parse(&text).ok_or_else(|| format!("Invalid due date \"{}\".", text))
+    }
+}
+
+impl From<Due> for String {
+    fn from(due: Due) -> Self {
+        due.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let due = Due::parse("2024-05-01 17:30").unwrap();
+
+        assert_eq!(due.to_string(), "2024-05-01 17:30");
+        assert_eq!(Due::parse("2024-05-01").unwrap().time, None);
+        assert!(Due::parse("tomorrow").is_none());
+        assert!(Due::parse("2024-05-01 5pm").is_none());
+    }
+
+    #[test]
+    fn state() {
+        let now = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap().and_hms_opt(12, 0, 0).unwrap();
+
+        assert_eq!(Due::parse("2024-04-30").unwrap().state_at(now), DueState::Overdue);
+        assert_eq!(Due::parse("2024-05-01").unwrap().state_at(now), DueState::Today);
+        assert_eq!(Due::parse("2024-05-01 11:00").unwrap().state_at(now), DueState::Overdue);
+        assert_eq!(Due::parse("2024-05-01 13:00").unwrap().state_at(now), DueState::Today);
+        assert_eq!(Due::parse("2024-05-02").unwrap().state_at(now), DueState::Later);
+    }
+}
@@ -0,0 +1,74 @@
+use bytebuffer::ByteErr;
+use std::{fmt, io};
+
+#[derive(Debug)]
+pub enum Error {
+    ///No entry exists with the given id.
+    InvalidId(usize),
+    ///The operation can not be done to the root of the list.
+    Root,
+    ///The entry can not be placed in the requested position, like inside of itself.
+    InvalidPosition,
+    ///A list file could not be read or written.
+    Io(io::Error),
+    ///A list file could not be decoded.
+    Bytes(ByteErr),
+    ///The file is not a todo list.
+    UnknownFormat,
+    ///The file was written by a newer version of the program.
+    UnsupportedVersion(u16),
+    ///A list could not be converted to or from json.
+    Json(serde_json::Error),
+    ///The text is not a due date.
+    InvalidDue(String),
+    ///A search is not a valid regular expression.
+    Regex(regex::Error),
+    ///Markdown and json files are only imported from and exported to, never saved over.
+    ImportOnly,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use Error::*;
+
+        match self {
+            InvalidId(id)   => write!(f, "No entry with id {}.", id),
+            Root            => write!(f, "The root entry can not be changed that way."),
+            InvalidPosition => write!(f, "The entry can not be placed there."),
+            Io(err)         => write!(f, "File error: {}", err),
+            Bytes(err)      => write!(f, "Could not read list: {:?}", err),
+            UnknownFormat   => write!(f, "The file is not a todo list."),
+            UnsupportedVersion(version) => write!(f, "The list uses file format version {} which is newer than this program.", version),
+            Json(err)       => write!(f, "Json error: {}", err),
+            InvalidDue(text) => write!(f, "\"{}\" is not a date like 2024-05-01 or 2024-05-01 17:30.", text),
+            Regex(err)      => write!(f, "Invalid search: {}", err),
+            ImportOnly      => write!(f, "Lists are not saved to .md or .json files. Export them instead."),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Error::Json(err)
+    }
+}
+
+impl From<regex::Error> for Error {
+    fn from(err: regex::Error) -> Self {
+        Error::Regex(err)
+    }
+}
+
+impl From<ByteErr> for Error {
+    fn from(err: ByteErr) -> Self {
+        Error::Bytes(err)
+    }
+}
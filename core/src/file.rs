@@ -0,0 +1,353 @@
+use super::*;
+
+use std::{fs, io::Write};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+///Every todo file written since version 2 starts with these bytes.
+const MAGIC: &[u8; 4] = b"TODO";
+
+///The format written by this version of the program.
+///Version 1 is the original format which had no header and is only read to be upgraded.
+pub const FORMAT_VERSION: u16 = 2;
+
+const HEADER_LEN: usize = 8;
+
+///Set in the header flags when completed entries are hidden.
+pub const FLAG_HIDE_COMPLETED: u16 = 1;
+
+///Describes the contents of a todo file.
+///Laid out as the magic bytes followed by the version and flags as little endian u16s.
+pub struct Header {
+    pub version: u16,
+    pub flags: u16,
+}
+
+impl Header {
+    pub fn new(flags: u16) -> Header {
+        Header {
+            version: FORMAT_VERSION,
+            flags: flags,
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&self.version.to_le_bytes());
+        bytes.extend_from_slice(&self.flags.to_le_bytes());
+        bytes
+    }
+
+    ///Splits the header from the rest of the file. Returns None if the file does not start with a header.
+    pub fn parse(bytes: &[u8]) -> Option<(Header, &[u8])> {
+        if bytes.len() < HEADER_LEN || &bytes[..4] != MAGIC { return None }
+
+        let header = Header {
+            version: u16::from_le_bytes([bytes[4], bytes[5]]),
+            flags:   u16::from_le_bytes([bytes[6], bytes[7]]),
+        };
+
+        Some((header, &bytes[HEADER_LEN..]))
+    }
+}
+
+///Reads the tree out of a todo file of any known version, upgrading it to the current entry layout.
+pub fn read_tree(bytes: &[u8]) -> Result<(Header, Tree<Entry>), Error> {
+    match Header::parse(bytes) {
+        Some((header, body)) => match header.version {
+            FORMAT_VERSION => Ok((header, read_whole(body)?)),
+            version => Err(Error::UnsupportedVersion(version)),
+        },
+        None => {
+            let tree = read_whole::<EntryV1>(bytes).map_err(|_| Error::UnknownFormat)?;
+            Ok((Header::new(0), upgrade_v1(&tree)?))
+        },
+    }
+}
+
+///Reads a tree that takes up all of the bytes. A tree without a root or bytes left after it mean this is not a list.
+fn read_whole<T>(bytes: &[u8]) -> Result<Tree<T>, Error> where Tree<T>: FromBytes {
+    let mut bytes = bytes.iter().copied();
+    let tree = Tree::<T>::from_bytes(&mut bytes)?;
+
+    if tree.get_root().is_none() || bytes.next().is_some() {
+        return Err(Error::UnknownFormat)
+    }
+
+    Ok(tree)
+}
+
+///The kinds of files a list can be saved to and loaded from, decided by the extension.
+#[derive(PartialEq, Clone, Copy)]
+pub enum FileKind {
+    Todo,
+    Markdown,
+    Json,
+}
+
+impl FileKind {
+    ///Returns None if the extension is not one that lists are saved as.
+    pub fn of(path: &Path) -> Option<FileKind> {
+        let ext = path.extension()?;
+
+        if ext.eq_ignore_ascii_case("todo") {
+            Some(FileKind::Todo)
+        }
+        else if ext.eq_ignore_ascii_case("md") {
+            Some(FileKind::Markdown)
+        }
+        else if ext.eq_ignore_ascii_case("json") {
+            Some(FileKind::Json)
+        }
+        else {
+            None
+        }
+    }
+}
+
+///Replaces the file at path with the given contents without ever leaving a partly written file behind.
+///The contents are written to a temporary file next to the target which is then renamed over it.
+///If backup is set the previous version of the file is kept with ".bak" added to its name.
+pub fn write_atomic(path: &Path, contents: &[u8], backup: bool) -> Result<(), Error> {
+    let temp = add_suffix(path, ".tmp");
+    let written = replace_with(path, &temp, contents, backup);
+
+    if written.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+
+    written
+}
+
+fn replace_with(path: &Path, temp: &Path, contents: &[u8], backup: bool) -> Result<(), Error> {
+    let mut file = fs::OpenOptions::new().write(true).create(true).truncate(true).open(temp)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+
+    if backup && path.is_file() {
+        fs::copy(path, add_suffix(path, ".bak"))?;
+    }
+
+    fs::rename(temp, path)?;
+    Ok(())
+}
+
+fn add_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().map(OsString::from).unwrap_or_default();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+///Tag of the notes in the optional fields of an entry.
+pub const FIELD_NOTES: u8 = 1;
+///Tag of the due date, stored as text like "2024-05-01 17:30".
+pub const FIELD_DUE: u8 = 2;
+///Tag of the priority, stored as a single byte.
+pub const FIELD_PRIORITY: u8 = 3;
+///Tag of whether the children of the entry are shown, stored as a single byte.
+pub const FIELD_FOLD: u8 = 4;
+
+///Adds a field to the optional fields of an entry.
+///Each field is its tag followed by the length of the value as a little endian u32 and the value.
+pub fn write_field(fields: &mut Vec<u8>, tag: u8, value: &[u8]) {
+    fields.push(tag);
+    fields.extend_from_slice(&(value.len() as u32).to_le_bytes());
+    fields.extend_from_slice(value);
+}
+
+///Splits the optional fields of an entry into tags and values. A field cut short ends the list.
+pub fn read_fields(mut fields: &[u8]) -> Vec<(u8, &[u8])> {
+    let mut out = Vec::new();
+
+    while fields.len() >= 5 {
+        let len = u32::from_le_bytes([fields[1], fields[2], fields[3], fields[4]]) as usize;
+        if fields.len() - 5 < len { break }
+
+        out.push((fields[0], &fields[5..5 + len]));
+        fields = &fields[5 + len..];
+    }
+
+    out
+}
+
+///Entry layout used by version 1 files.
+struct EntryV1 {
+    name: String,
+    complete: bool,
+}
+
+impl FromBytes for EntryV1 {
+    fn from_bytes<T: Iterator<Item = u8>>(bytes: &mut T) -> Result<Self, ByteErr>{
+        Ok(EntryV1{
+            name: String::from(String::from_utf8_lossy(&Vec::from_bytes(bytes)?)),
+            complete: bool::from_bytes(bytes)?,
+        })
+    }
+
+    fn from_io_bytes<T: Iterator<Item = Result<u8, std::io::Error>>>(bytes: &mut T) -> Result<Self, ByteErr>{
+        Ok(EntryV1{
+            name: String::from(String::from_utf8_lossy(&Vec::from_io_bytes(bytes)?)),
+            complete: bool::from_io_bytes(bytes)?,
+        })
+    }
+}
+
+fn upgrade_v1(old: &Tree<EntryV1>) -> Result<Tree<Entry>, Error> {
+    let old_root = old.get_root().ok_or(Error::UnknownFormat)?;
+    let mut tree = Tree::new_with_root(upgrade_entry_v1(old.data_at(old_root).map_err(|_| Error::UnknownFormat)?));
+    let root = tree.get_root().unwrap();
+
+    upgrade_children_v1(old, old_root, &mut tree, root)?;
+    Ok(tree)
+}
+
+fn upgrade_children_v1(old: &Tree<EntryV1>, old_id: usize, tree: &mut Tree<Entry>, id: usize) -> Result<(), Error> {
+    for old_child in old.children_of(old_id).map_err(|_| Error::UnknownFormat)? {
+        let entry = upgrade_entry_v1(old.data_at(old_child).map_err(|_| Error::UnknownFormat)?);
+        tree.new_node(entry, Position::LastChild, id).map_err(|_| Error::UnknownFormat)?;
+
+        let child = tree.children_of(id).map_err(|_| Error::UnknownFormat)?.into_iter().last().unwrap();
+        upgrade_children_v1(old, old_child, tree, child)?;
+    }
+
+    Ok(())
+}
+
+fn upgrade_entry_v1(old: &EntryV1) -> Entry {
+    Entry::new(old.name.clone(), old.complete)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_round_trip() {
+        let mut bytes = Header::new(3).to_bytes();
+        bytes.push(42);
+
+        let (header, body) = Header::parse(&bytes).unwrap();
+
+        assert_eq!(header.version, FORMAT_VERSION);
+        assert_eq!(header.flags, 3);
+        assert_eq!(body, &[42]);
+    }
+
+    #[test]
+    fn fields_round_trip() {
+        let mut fields = Vec::new();
+        write_field(&mut fields, FIELD_NOTES, b"notes");
+        write_field(&mut fields, 200, b"");
+
+        assert_eq!(read_fields(&fields), vec![(FIELD_NOTES, &b"notes"[..]), (200, &b""[..])]);
+        assert_eq!(read_fields(&fields[..4]), vec![]);
+    }
+
+    #[test]
+    fn no_header() {
+        assert!(Header::parse(b"TOD").is_none());
+        assert!(Header::parse(b"not a todo file").is_none());
+    }
+
+    impl<'a> IntoBytes<'a> for EntryV1 {
+        fn into_bytes(&'a self) -> Box<dyn Iterator<Item = u8> + 'a> {
+            Box::new(self.name.as_bytes().into_bytes().chain(self.complete.into_bytes()))
+        }
+    }
+
+    fn v1(name: &str, complete: bool) -> EntryV1 {
+        EntryV1{name: String::from(name), complete: complete}
+    }
+
+    #[test]
+    fn upgrade_version_1() {
+        let mut old = Tree::new_with_root(v1("list", false));
+        let root = old.get_root().unwrap();
+        old.new_node(v1("a", true), Position::LastChild, root).unwrap();
+        old.new_node(v1("b", false), Position::LastChild, root).unwrap();
+        let b = old.children_of(root).unwrap().into_iter().last().unwrap();
+        old.new_node(v1("b1", false), Position::LastChild, b).unwrap();
+
+        let bytes: Vec<u8> = old.into_bytes().collect();
+        let (header, tree) = read_tree(&bytes).unwrap();
+        let core = Core::from_tree(tree);
+
+        assert_eq!(header.version, FORMAT_VERSION);
+        assert_eq!(header.flags, 0);
+        assert_eq!(core.name(), "list");
+
+        let entries: Vec<(String, bool)> = core.pre_order().unwrap().into_iter()
+            .map(|id| core.get_entry(id).unwrap())
+            .map(|entry| (entry.name.clone(), entry.complete))
+            .collect();
+        assert_eq!(entries, vec![
+            (String::from("list"), false),
+            (String::from("a"), true),
+            (String::from("b"), false),
+            (String::from("b1"), false),
+        ]);
+    }
+
+    #[test]
+    fn newer_version() {
+        let mut bytes = Header{version: FORMAT_VERSION + 1, flags: 0}.to_bytes();
+        bytes.extend_from_slice(&[1, 2, 3]);
+
+        assert!(matches!(read_tree(&bytes), Err(Error::UnsupportedVersion(version)) if version == FORMAT_VERSION + 1));
+    }
+
+    #[test]
+    fn unknown_format() {
+        assert!(matches!(read_tree(&[]), Err(Error::UnknownFormat)));
+        assert!(matches!(read_tree(&[0xff, 0xff, 0xff]), Err(Error::UnknownFormat)));
+    }
+
+    #[test]
+    fn trailing_bytes() {
+        let old = Tree::new_with_root(v1("list", false));
+        let mut bytes: Vec<u8> = old.into_bytes().collect();
+        bytes.push(0);
+
+        assert!(matches!(read_tree(&bytes), Err(Error::UnknownFormat)));
+
+        let mut bytes: Vec<u8> = Core::new(String::from("list")).save().collect();
+        bytes.push(0);
+
+        assert!(matches!(read_tree(&bytes), Err(Error::UnknownFormat)));
+    }
+
+    #[test]
+    fn save_round_trip() {
+        let mut core = Core::new(String::from("list"));
+        let root = core.current_root();
+
+        let parent = core.new_entry(String::from("Parent"), Position::LastChild, root).unwrap();
+        let child = core.new_entry(String::from("Child"), Position::LastChild, parent).unwrap();
+        core.set_notes(parent, String::from("first line\nsecond line")).unwrap();
+        core.set_due(parent, Due::parse("2024-05-01 17:30")).unwrap();
+        core.set_priority(child, Priority::High).unwrap();
+        core.set_fold(parent, Fold::Collapsed).unwrap();
+        core.set_complete(child).unwrap();
+        core.set_hide_completed(true);
+
+        let bytes: Vec<u8> = core.save().collect();
+        let (header, tree) = read_tree(&bytes).unwrap();
+        let loaded = Core::from_tree(tree);
+
+        assert_eq!(header.flags, FLAG_HIDE_COMPLETED);
+
+        let ids = core.pre_order().unwrap();
+        let loaded_ids = loaded.pre_order().unwrap();
+        assert_eq!(ids.len(), loaded_ids.len());
+
+        for (&id, &loaded_id) in ids.iter().zip(&loaded_ids) {
+            assert!(core.get_entry(id).unwrap() == loaded.get_entry(loaded_id).unwrap());
+        }
+
+        let loaded_parent = loaded.get_entry(loaded_ids[1]).unwrap();
+        assert_eq!(loaded_parent.notes, "first line\nsecond line");
+        assert_eq!(loaded_parent.due, Due::parse("2024-05-01 17:30"));
+        assert_eq!(loaded_parent.fold, Fold::Collapsed);
+        assert_eq!(loaded.get_entry(loaded_ids[2]).unwrap().priority, Priority::High);
+    }
+}
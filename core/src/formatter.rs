@@ -1,8 +1,45 @@
-use super::Entry;
+use super::{Core, Entry, Error, Priority, Progress, ProgressStyle, ProgressCount, State};
 use serde::{Serialize, Deserialize};
 
 pub trait Format{
-    fn format(&self, entry: &Entry, depth: usize, has_child: bool) -> String;
+    ///Progress is only given for entries with children and only shown if the formatter is set to show it.
+    fn format(&self, entry: &Entry, state: State, depth: usize, has_child: bool, progress: Option<Progress>) -> String;
+}
+
+///Added after the name of entries that have notes.
+const NOTES_MARKER: &str = " ✎";
+
+///Shown before the name of the entry.
+fn priority_marker(entry: &Entry) -> &'static str {
+    match entry.priority {
+        Priority::None   => "",
+        Priority::Low    => "! ",
+        Priority::Medium => "!! ",
+        Priority::High   => "!!! ",
+    }
+}
+
+///Everything shown after the name of the entry.
+fn details(entry: &Entry, progress: Option<Progress>, style: ProgressStyle) -> String {
+    let mut details = String::new();
+
+    if let Some(progress) = progress {
+        match style {
+            ProgressStyle::Off     => (),
+            ProgressStyle::Count   => details.push_str(&format!(" [{}/{}]", progress.done, progress.total)),
+            ProgressStyle::Percent => details.push_str(&format!(" {}%", progress.percent())),
+        }
+    }
+
+    if let Some(due) = entry.due {
+        details.push_str(&format!(" (due {})", due));
+    }
+
+    if entry.has_notes() {
+        details.push_str(NOTES_MARKER);
+    }
+
+    details
 }
 
 #[derive(Clone)]
@@ -10,21 +47,27 @@ pub struct Formatter {
     indent: usize,
     completed: char,
     incomplete: char,
+    partial: char,
+    progress: ProgressStyle,
+    count: ProgressCount,
     formatter: InternalFormatter,
 }
 
 impl Formatter {
-    pub fn new(indent: usize, completed: char, incomplete: char, form_type: Type) -> Self {
+    pub fn new(indent: usize, completed: char, incomplete: char, partial: char, form_type: Type) -> Self {
         Formatter {
-            formatter: form_type.into_formatter(indent, completed, incomplete),
+            formatter: form_type.into_formatter(indent, completed, incomplete, partial, ProgressStyle::Off),
             indent: indent,
             completed: completed,
             incomplete: incomplete,
+            partial: partial,
+            progress: ProgressStyle::Off,
+            count: ProgressCount::Leaves,
         }
     }
 
     pub fn default() -> Self {
-        Formatter::new(1, 'X', ' ', Type::Fancy)
+        Formatter::new(1, 'X', ' ', '-', Type::Fancy)
     }
 
     pub fn get_indent(&self) -> usize {
@@ -39,11 +82,50 @@ impl Formatter {
         self.incomplete
     }
 
+    pub fn get_partial(&self) -> char {
+        self.partial
+    }
+
     pub fn get_type(&self) -> Type {
         self.formatter.get_type()
     }
 
-    pub fn set(&mut self, indent: Option<usize>, completed: Option<char>, incomplete: Option<char>, form_type: Option<Type>) {
+    pub fn get_progress(&self) -> ProgressStyle {
+        self.progress
+    }
+
+    pub fn get_count(&self) -> ProgressCount {
+        self.count
+    }
+
+    pub fn set_progress(&mut self, progress: Option<ProgressStyle>, count: Option<ProgressCount>) {
+        if let Some(count) = count {
+            self.count = count;
+        }
+
+        if let Some(progress) = progress {
+            self.progress = progress;
+            self.formatter = self.formatter.get_type().into_formatter(self.indent, self.completed, self.incomplete, self.partial, self.progress);
+        }
+    }
+
+    ///Formats an entry of the list with its state and its progress counted the way the formatter is set to.
+    pub fn format_entry(&self, core: &Core, id: usize, depth: usize, has_child: bool) -> Result<String, Error> {
+        let progress = match self.progress {
+            ProgressStyle::Off => None,
+            _ => core.progress(id, self.count)?,
+        };
+
+        Ok(self.format(core.get_entry(id)?, core.state(id)?, depth, has_child, progress))
+    }
+
+    ///The notes of the entry as lines indented past the entry, for printing under it.
+    pub fn format_notes(&self, entry: &Entry, depth: usize) -> Vec<String> {
+        let indent = " ".repeat((self.indent + 1) * (depth + 1));
+        entry.notes.lines().map(|line| format!("{}{}", indent, line)).collect()
+    }
+
+    pub fn set(&mut self, indent: Option<usize>, completed: Option<char>, incomplete: Option<char>, partial: Option<char>, form_type: Option<Type>) {
         let mut change = false;
 
         if let Some(indent) = indent {
@@ -61,6 +143,11 @@ impl Formatter {
             change = true;
         }
 
+        if let Some(partial) = partial {
+            self.partial = partial;
+            change = true;
+        }
+
         if change || form_type.is_some() {
             let new_type = if let Some(form_type) = form_type {
                 form_type
@@ -69,18 +156,18 @@ impl Formatter {
                 self.formatter.get_type()
             };
 
-            self.formatter = new_type.into_formatter(self.indent, self.completed, self.incomplete);
+            self.formatter = new_type.into_formatter(self.indent, self.completed, self.incomplete, self.partial, self.progress);
         }
     }
 }
 
 impl Format for Formatter {
-    fn format(&self, entry: &Entry, depth: usize, has_child: bool) -> String {
+    fn format(&self, entry: &Entry, state: State, depth: usize, has_child: bool, progress: Option<Progress>) -> String {
         use InternalFormatter::*;
 
         match self.formatter {
-            Basic(ref x) => x.format(entry, depth, has_child),
-            Fancy(ref x) => x.format(entry, depth, has_child),
+            Basic(ref x) => x.format(entry, state, depth, has_child, progress),
+            Fancy(ref x) => x.format(entry, state, depth, has_child, progress),
         }
     }
 }
@@ -92,12 +179,12 @@ pub enum Type {
 }
 
 impl Type {
-    fn into_formatter(self, indent: usize, completed: char, incomplete: char) -> InternalFormatter {
+    fn into_formatter(self, indent: usize, completed: char, incomplete: char, partial: char, progress: ProgressStyle) -> InternalFormatter {
         use InternalFormatter::*;
 
         match self {
-            Type::Basic => Basic(BasicForm::new(indent, completed, incomplete)),
-            Type::Fancy => Fancy(FancyForm::new(indent, completed, incomplete)),
+            Type::Basic => Basic(BasicForm::new(indent, completed, incomplete, partial, progress)),
+            Type::Fancy => Fancy(FancyForm::new(indent, completed, incomplete, partial, progress)),
         }
     }
 }
@@ -124,33 +211,38 @@ pub struct BasicForm{
     indent: String,
     completed: char,
     incomplete: char,
+    partial: char,
+    progress: ProgressStyle,
 }
 
 impl BasicForm{
-    pub fn new(indent: usize, completed: char, incomplete: char) -> Self {
+    pub fn new(indent: usize, completed: char, incomplete: char, partial: char, progress: ProgressStyle) -> Self {
         BasicForm{
             indent: format!("{}", " ".repeat(indent)),
             completed: completed,
             incomplete: incomplete,
+            partial: partial,
+            progress: progress,
         }
     }
 
-    fn comp(&self, complete: bool) -> char {
-        if complete{
-            self.completed
-        }
-        else{
-            self.incomplete
+    fn comp(&self, state: State) -> char {
+        match state {
+            State::Complete   => self.completed,
+            State::Partial    => self.partial,
+            State::Incomplete => self.incomplete,
         }
     }
 }
 
 impl Format for BasicForm{
-    fn format(&self, entry: &Entry, depth: usize, _has_child: bool) -> String{
-        format!("{}[{}]: {}",
+    fn format(&self, entry: &Entry, state: State, depth: usize, _has_child: bool, progress: Option<Progress>) -> String{
+        format!("{}[{}]: {}{}{}",
             self.indent.repeat(depth),
-            self.comp(entry.complete),
+            self.comp(state),
+            priority_marker(entry),
             entry.name,
+            details(entry, progress, self.progress),
         )
     }
 }
@@ -161,24 +253,27 @@ pub struct FancyForm{
     padding: String,
     completed: char,
     incomplete: char,
+    partial: char,
+    progress: ProgressStyle,
 }
 
 impl FancyForm{
-    pub fn new(indent: usize, completed: char, incomplete: char) -> Self {
+    pub fn new(indent: usize, completed: char, incomplete: char, partial: char, progress: ProgressStyle) -> Self {
         FancyForm{
             connecter: format!("├{}", "─".repeat(indent)),
             padding: format!("│{}", " ".repeat(indent)),
             completed: completed,
             incomplete: incomplete,
+            partial: partial,
+            progress: progress,
         }
     }
 
-    fn comp(&self, complete: bool) -> char {
-        if complete{
-            self.completed
-        }
-        else{
-            self.incomplete
+    fn comp(&self, state: State) -> char {
+        match state {
+            State::Complete   => self.completed,
+            State::Partial    => self.partial,
+            State::Incomplete => self.incomplete,
         }
     }
 
@@ -211,11 +306,13 @@ impl FancyForm{
 }
 
 impl Format for FancyForm{
-    fn format(&self, entry: &Entry, depth: usize, has_child: bool) -> String {
-        format!("{}[{}]: {}",
+    fn format(&self, entry: &Entry, state: State, depth: usize, has_child: bool, progress: Option<Progress>) -> String {
+        format!("{}[{}]: {}{}{}",
             self.pipes(depth, has_child),
-            self.comp(entry.complete),
+            self.comp(state),
+            priority_marker(entry),
             entry.name,
+            details(entry, progress, self.progress),
         )
     }
 }
@@ -0,0 +1,161 @@
+use super::Entry;
+use std::collections::VecDeque;
+use serde::{Serialize, Deserialize};
+
+///How many changes are kept before the oldest ones are forgotten.
+pub const HISTORY_LIMIT: usize = 100;
+
+///An entry and all of its descendants detached from the tree.
+///This is also the layout used for json with the entry fields next to the list of children.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct Branch {
+    #[serde(flatten)]
+    pub entry: Entry,
+    #[serde(default)]
+    pub children: Vec<Branch>,
+}
+
+///A reversible change to the tree.
+///Entries are located by their path of child indexes from the root because ids do not survive removing and reinserting a sub tree.
+#[derive(Clone)]
+pub enum Command {
+    Insert{path: Vec<usize>, branch: Branch},
+    Remove{path: Vec<usize>, branch: Branch},
+    Move{from: Vec<usize>, to: Vec<usize>},
+    Edit{path: Vec<usize>, before: Entry, after: Entry},
+    ///Completion of every entry in pre order. Toggling can change entries all over the tree so the whole state is kept.
+    Complete{path: Vec<usize>, before: Vec<bool>, after: Vec<bool>},
+    ///Replaces all children of an entry, used when they are sorted.
+    Reorder{path: Vec<usize>, before: Vec<Branch>, after: Vec<Branch>},
+    ///Changes that are undone and redone together, applied in order.
+    Group(Vec<Command>),
+}
+
+impl Command {
+    ///Returns the command that undoes this one.
+    pub fn inverse(self) -> Command {
+        use Command::*;
+
+        match self {
+            Insert{path, branch}          => Remove{path: path, branch: branch},
+            Remove{path, branch}          => Insert{path: path, branch: branch},
+            Move{from, to}                => Move{from: to, to: from},
+            Edit{path, before, after}     => Edit{path: path, before: after, after: before},
+            Complete{path, before, after} => Complete{path: path, before: after, after: before},
+            Reorder{path, before, after}  => Reorder{path: path, before: after, after: before},
+            Group(commands)               => Group(commands.into_iter().rev().map(Command::inverse).collect()),
+        }
+    }
+}
+
+pub struct History {
+    undo: VecDeque<Command>,
+    redo: Vec<Command>,
+    limit: usize,
+}
+
+impl History {
+    pub fn new(limit: usize) -> History {
+        History {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            limit: limit,
+        }
+    }
+
+    ///Adds a newly done change. Anything that was undone can no longer be redone.
+    pub fn record(&mut self, command: Command) {
+        self.redo.clear();
+        self.push_undo(command);
+    }
+
+    ///Adds a change to the last one so both are undone in a single step.
+    pub fn join(&mut self, command: Command) {
+        match self.undo.pop_back() {
+            Some(last) => self.record(Command::Group(vec![last, command])),
+            None => self.record(command),
+        }
+    }
+
+    ///Returns the last change so it can be reverted.
+    pub fn undo(&mut self) -> Option<Command> {
+        let command = self.undo.pop_back()?;
+        self.redo.push(command.clone());
+
+        Some(command)
+    }
+
+    ///Returns the last reverted change so it can be applied again.
+    pub fn redo(&mut self) -> Option<Command> {
+        let command = self.redo.pop()?;
+        self.push_undo(command.clone());
+
+        Some(command)
+    }
+
+    fn push_undo(&mut self, command: Command) {
+        self.undo.push_back(command);
+
+        if self.undo.len() > self.limit {
+            self.undo.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(index: usize) -> Command {
+        Command::Move{from: vec![index], to: vec![index]}
+    }
+
+    fn index(command: Option<Command>) -> Option<usize> {
+        match command? {
+            Command::Move{from, ..} => Some(from[0]),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn limit() {
+        let mut history = History::new(2);
+
+        for i in 0..3 {
+            history.record(step(i));
+        }
+
+        assert_eq!(index(history.undo()), Some(2));
+        assert_eq!(index(history.undo()), Some(1));
+        assert_eq!(index(history.undo()), None);
+    }
+
+    #[test]
+    fn record_clears_redo() {
+        let mut history = History::new(HISTORY_LIMIT);
+
+        history.record(step(0));
+        history.record(step(1));
+        history.undo();
+
+        history.record(step(2));
+        assert_eq!(index(history.redo()), None);
+        assert_eq!(index(history.undo()), Some(2));
+        assert_eq!(index(history.undo()), Some(0));
+    }
+
+    #[test]
+    fn join() {
+        let mut history = History::new(HISTORY_LIMIT);
+
+        history.record(step(0));
+        history.record(step(1));
+        history.join(step(2));
+
+        match history.undo() {
+            Some(Command::Group(commands)) => assert_eq!(commands.len(), 2),
+            _ => panic!("joined changes were not grouped"),
+        }
+        assert_eq!(index(history.undo()), Some(0));
+    }
+}
@@ -10,40 +10,219 @@
 
 use bytebuffer::*;
 
-use std::{fs, io::Read};
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 use tree;
 pub use tree::*;
 
+use serde::{Serialize, Deserialize};
+
 mod formatter;
 pub use formatter::*;
 
-#[derive(Clone)]
+mod history;
+pub use history::Branch;
+use history::*;
+
+mod error;
+pub use error::Error;
+
+mod file;
+pub use file::{FORMAT_VERSION, FileKind, write_atomic};
+use file::*;
+
+mod markdown;
+
+mod due;
+pub use due::{Due, DueState};
+
+mod sort;
+pub use sort::SortKey;
+
+mod progress;
+pub use progress::{Progress, ProgressStyle, ProgressCount};
+
+mod state;
+pub use state::State;
+
+mod tags;
+
+mod view;
+pub use view::Fold;
+
+mod search;
+pub use search::Query;
+
+mod outline;
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct Entry{
     pub name: String,
+    ///Entries without it in json are incomplete.
+    #[serde(default)]
     pub complete: bool,
+    ///Free text kept with the entry. Empty if the entry has no notes.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub notes: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<Due>,
+    #[serde(default, skip_serializing_if = "Priority::is_none")]
+    pub priority: Priority,
+    #[serde(default, skip_serializing_if = "Fold::is_depth")]
+    pub fold: Fold,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    None,
+    Low,
+    Medium,
+    High,
+}
+
+impl Priority {
+    pub fn is_none(&self) -> bool {
+        *self == Priority::None
+    }
+
+    ///The next higher priority, going back to none after high.
+    pub fn next(self) -> Priority {
+        use Priority::*;
+
+        match self {
+            None   => Low,
+            Low    => Medium,
+            Medium => High,
+            High   => None,
+        }
+    }
+
+    fn to_byte(self) -> u8 {
+        self as u8
+    }
+
+    fn from_byte(byte: u8) -> Option<Priority> {
+        use Priority::*;
+
+        match byte {
+            0 => Some(None),
+            1 => Some(Low),
+            2 => Some(Medium),
+            3 => Some(High),
+            _ => Option::None,
+        }
+    }
+}
+
+impl Default for Priority {
+    fn default() -> Self {
+        Priority::None
+    }
+}
+
+impl Entry {
+    pub fn new(name: String, complete: bool) -> Entry {
+        Entry{
+            name: name,
+            complete: complete,
+            notes: String::new(),
+            due: None,
+            priority: Priority::None,
+            fold: Fold::Depth,
+        }
+    }
+
+    pub fn has_notes(&self) -> bool {
+        !self.notes.is_empty()
+    }
+
+    ///Complete entries are never overdue.
+    pub fn is_overdue(&self) -> bool {
+        !self.complete && self.due_state() == Some(DueState::Overdue)
+    }
+
+    pub fn due_state(&self) -> Option<DueState> {
+        self.due.map(|due| due.state())
+    }
+
+    fn fields(&self) -> Vec<u8> {
+        let mut fields = Vec::new();
+
+        if self.has_notes() {
+            write_field(&mut fields, FIELD_NOTES, self.notes.as_bytes());
+        }
+
+        if let Some(due) = self.due {
+            write_field(&mut fields, FIELD_DUE, due.to_string().as_bytes());
+        }
+
+        if !self.priority.is_none() {
+            write_field(&mut fields, FIELD_PRIORITY, &[self.priority.to_byte()]);
+        }
+
+        if !self.fold.is_depth() {
+            write_field(&mut fields, FIELD_FOLD, &[self.fold.to_byte()]);
+        }
+
+        fields
+    }
+
+    fn set_fields(&mut self, fields: &[u8]) {
+        for (tag, value) in read_fields(fields) {
+            match tag {
+                FIELD_NOTES => self.notes = String::from(String::from_utf8_lossy(value)),
+                FIELD_DUE   => self.due = Due::parse(&String::from_utf8_lossy(value)),
+                FIELD_PRIORITY => {
+                    if let Some(priority) = value.first().copied().and_then(Priority::from_byte) {
+                        self.priority = priority;
+                    }
+                },
+                FIELD_FOLD => {
+                    if let Some(fold) = value.first().copied().and_then(Fold::from_byte) {
+                        self.fold = fold;
+                    }
+                },
+                //fields from newer versions are dropped.
+                _ => (),
+            }
+        }
+    }
 }
 
+//Entries are written as the name and completion followed by a block of optional fields.
+//New fields go into the block so older files can still be read without them.
 impl<'a> IntoBytes<'a> for Entry {
     fn into_bytes(&'a self) -> Box<dyn Iterator<Item = u8> + 'a> {
-        Box::new(self.name.as_bytes().into_bytes().chain(self.complete.into_bytes()))
+        let fields = self.fields();
+
+        Box::new(self.name.as_bytes().into_bytes()
+            .chain(self.complete.into_bytes())
+            .chain(fields.as_slice().into_bytes().collect::<Vec<u8>>())
+        )
     }
 }
 
 impl FromBytes for Entry {
     fn from_bytes<T: Iterator<Item = u8>>(bytes: &mut T) -> Result<Self, ByteErr>{
-        Ok(Entry{
-            name: String::from(String::from_utf8_lossy(&Vec::from_bytes(bytes)?)),
-            complete: bool::from_bytes(bytes)?,
-        })
+        let mut entry = Entry::new(
+            String::from(String::from_utf8_lossy(&Vec::from_bytes(bytes)?)),
+            bool::from_bytes(bytes)?,
+        );
+        entry.set_fields(&Vec::from_bytes(bytes)?);
+
+        Ok(entry)
     }
 
     fn from_io_bytes<T: Iterator<Item = Result<u8, std::io::Error>>>(bytes: &mut T) -> Result<Self, ByteErr>{
-        Ok(Entry{
-            name: String::from(String::from_utf8_lossy(&Vec::from_io_bytes(bytes)?)),
-            complete: bool::from_io_bytes(bytes)?,
-        })
+        let mut entry = Entry::new(
+            String::from(String::from_utf8_lossy(&Vec::from_io_bytes(bytes)?)),
+            bool::from_io_bytes(bytes)?,
+        );
+        entry.set_fields(&Vec::from_io_bytes(bytes)?);
+
+        Ok(entry)
     }
 }
 
@@ -51,41 +230,87 @@ pub struct Core{
     roots: Vec<usize>,
     depth: usize,
     tree: Tree<Entry>,
+    history: History,
+    modified: bool,
+    ///Tag the shown entries are filtered by.
+    filter: Option<String>,
+    hide_completed: bool,
 }
 
 impl Core{
     pub fn new(name: String) -> Core {
         Core{
-            tree: Tree::new_with_root(Entry{
-                name: name,
-                complete: false
-            }),
+            tree: Tree::new_with_root(Entry::new(name, false)),
             roots: vec![0],
             depth: 2,
+            history: History::new(HISTORY_LIMIT),
+            modified: true,
+            filter: None,
+            hide_completed: false,
         }
     }
 
-    pub fn get_entry_ids(&self) -> Vec<usize> {
-        self.tree.sub_tree_depth(*self.roots.last().unwrap(), self.depth).unwrap()
+    pub fn get_entry_ids(&self) -> Result<Vec<usize>, Error> {
+        Ok(self.view_info()?.into_iter().map(|info| info.id).collect())
     }
 
-    pub fn get_entries_info(&self) -> Vec<NodeInfo> {
-        self.tree.sub_tree_depth_info(*self.roots.last().unwrap(), self.depth).unwrap()
+    ///The entries that are shown starting with the current root. When filtered the depth and folds are ignored.
+    pub fn get_entries_info(&self) -> Result<Vec<NodeInfo>, Error> {
+        self.view_info()
     }
 
-    pub fn new_entry(&mut self, name: String, pos: Position, id: usize) {
-        self.tree.new_node(
-            Entry{
-                name: name,
-                complete: false,
-            },
-            pos,
-            id,
-        ).unwrap();
+    ///Adds a new entry in the position relative to id. Returns the id of the new entry.
+    pub fn new_entry(&mut self, name: String, pos: Position, id: usize) -> Result<usize, Error> {
+        let entry = Entry::new(name, false);
+
+        self.tree.new_node(entry.clone(), pos, id).map_err(|_| Error::InvalidId(id))?;
+
+        let path = self.inserted_path(pos, id)?;
+        let new_id = self.id_at(&path)?;
+        self.record(Command::Insert{
+            path: path,
+            branch: Branch{entry: entry, children: Vec::new()},
+        });
+
+        Ok(new_id)
+    }
+
+    pub fn get_entry(&self, id: usize) -> Result<&Entry, Error> {
+        self.tree.data_at(id).map_err(|_| Error::InvalidId(id))
+    }
+
+    fn get_entry_mut(&mut self, id: usize) -> Result<&mut Entry, Error> {
+        self.tree.data_at_mut(id).map_err(|_| Error::InvalidId(id))
     }
 
-    pub fn get_entry(&self, id: usize) -> &Entry{
-        self.tree.data_at(id).unwrap()
+    pub fn rename(&mut self, id: usize, name: String) -> Result<(), Error> {
+        self.edit(id, |entry| entry.name = name)
+    }
+
+    ///Replaces the notes of the entry. Empty notes remove them.
+    pub fn set_notes(&mut self, id: usize, notes: String) -> Result<(), Error> {
+        self.edit(id, |entry| entry.notes = notes)
+    }
+
+    pub fn set_due(&mut self, id: usize, due: Option<Due>) -> Result<(), Error> {
+        self.edit(id, |entry| entry.due = due)
+    }
+
+    pub fn set_priority(&mut self, id: usize, priority: Priority) -> Result<(), Error> {
+        self.edit(id, |entry| entry.priority = priority)
+    }
+
+    ///All incomplete entries in the list that are past their due date, in list order.
+    pub fn overdue(&self) -> Result<Vec<usize>, Error> {
+        let mut ids = Vec::new();
+
+        for id in self.pre_order()? {
+            if self.get_entry(id)?.is_overdue() {
+                ids.push(id);
+            }
+        }
+
+        Ok(ids)
     }
 
     pub fn inc_depth(&mut self, amount: usize) {
@@ -105,99 +330,672 @@ impl Core{
         self.depth
     }
 
-    pub fn parent_id(&self, id: usize) -> usize{
-        self.tree.parent_of(id).unwrap().unwrap()
+    pub fn parent_id(&self, id: usize) -> Result<usize, Error> {
+        self.parent_of(id)?.ok_or(Error::Root)
     }
 
-    fn set_comp_down(&mut self, id: usize, new: bool) -> bool {
-        let mut data = self.tree.data_at_mut(id).unwrap();
-        if data.complete == new { return false }
+    fn parent_of(&self, id: usize) -> Result<Option<usize>, Error> {
+        self.tree.parent_of(id).map_err(|_| Error::InvalidId(id))
+    }
+
+    pub fn children_of(&self, id: usize) -> Result<Vec<usize>, Error> {
+        Ok(self.tree.children_of(id).map_err(|_| Error::InvalidId(id))?.into_iter().collect())
+    }
+
+    ///The root of the whole tree. Every list is created with a root so it is always there.
+    fn tree_root(&self) -> usize {
+        self.tree.get_root().unwrap()
+    }
+
+    fn set_comp_down(&mut self, id: usize, new: bool) -> Result<bool, Error> {
+        let data = self.get_entry_mut(id)?;
+        if data.complete == new { return Ok(false) }
 
         data.complete = new;
 
-        for child in self.tree.children_of(id).unwrap(){
-            self.set_comp_down(child, new);
+        for child in self.children_of(id)? {
+            self.set_comp_down(child, new)?;
         }
 
-        true
+        Ok(true)
     }
 
-    pub fn set_incomplete(&mut self, id: usize){
-        if !self.set_comp_down(id, false) { return }
+    pub fn set_incomplete(&mut self, id: usize) -> Result<(), Error> {
+        let before = self.completion()?;
+        self.set_incomplete_inner(id)?;
+        self.record_completion(id, before)
+    }
+
+    pub fn set_complete(&mut self, id: usize) -> Result<(), Error> {
+        let before = self.completion()?;
+        self.set_complete_inner(id)?;
+        self.record_completion(id, before)
+    }
+
+    fn set_incomplete_inner(&mut self, id: usize) -> Result<(), Error> {
+        if !self.set_comp_down(id, false)? { return Ok(()) }
         
         let mut current = id;
-        while let Some(parent) = self.tree.parent_of(current).unwrap() {
-            let mut data = self.tree.data_at_mut(parent).unwrap();
-            if !data.complete { return }
+        while let Some(parent) = self.parent_of(current)? {
+            let data = self.get_entry_mut(parent)?;
+            if !data.complete { return Ok(()) }
     
             data.complete = false;
 
             current = parent;
         }
+
+        Ok(())
     }
 
-    pub fn set_complete(&mut self, id: usize){
-        if !self.set_comp_down(id, true) { return }
+    fn set_complete_inner(&mut self, id: usize) -> Result<(), Error> {
+        if !self.set_comp_down(id, true)? { return Ok(()) }
 
         let mut current = id;
-        while let Some(parent) = self.tree.parent_of(current).unwrap() {
-            if self.tree.data_at(parent).unwrap().complete { return }
+        while let Some(parent) = self.parent_of(current)? {
+            if self.get_entry(parent)?.complete { return Ok(()) }
 
-            for child in self.tree.children_of(parent).unwrap() {
-                if !self.tree.data_at(child).unwrap().complete { return }
+            for child in self.children_of(parent)? {
+                if !self.get_entry(child)?.complete { return Ok(()) }
             }
     
-            self.tree.data_at_mut(parent).unwrap().complete = true;
+            self.get_entry_mut(parent)?.complete = true;
 
             current = parent;
         }
+
+        Ok(())
     }
 
-    pub fn toggle_comp(&mut self, id: usize) {
-        if self.tree.data_at(id).unwrap().complete {
-            self.set_incomplete(id);
+    pub fn toggle_comp(&mut self, id: usize) -> Result<(), Error> {
+        if self.get_entry(id)?.complete {
+            self.set_incomplete(id)
         }
         else{
-            self.set_complete(id);
+            self.set_complete(id)
         }
     }
 
     pub fn name(&self) -> &str {
-        &self.tree.data_at(self.tree.get_root().unwrap()).unwrap().name
+        &self.tree.data_at(self.tree_root()).unwrap().name
     }
 
+    ///Returns the contents of a todo file for the list, starting with a header describing the format.
     pub fn save<'a>(&'a self) -> Box<dyn Iterator<Item = u8> + 'a>{
-        self.tree.into_bytes()
+        Box::new(Header::new(self.flags()).to_bytes().into_iter().chain(self.tree.into_bytes()))
     }
 
-    pub fn load(file_name: &PathBuf) -> Result<Core, ByteErr> {
-        let file = fs::OpenOptions::new().read(true).open(file_name)?;
-        let tree = Tree::from_io_bytes(&mut file.bytes())?;
+    ///Writes the list to a todo file. The old file is only replaced once the new one has been fully written.
+    ///".md" and ".json" files are refused since saving over them would lose everything those formats can not hold.
+    pub fn save_file(&mut self, path: &Path, backup: bool) -> Result<(), Error> {
+        match FileKind::of(path) {
+            Some(FileKind::Markdown) | Some(FileKind::Json) => return Err(Error::ImportOnly),
+            _ => (),
+        }
+
+        let contents: Vec<u8> = self.save().collect();
+        write_atomic(path, &contents, backup)?;
+
+        self.modified = false;
+        Ok(())
+    }
 
-        Ok(Core{
+    ///Returns true if the list has changed since it was last saved or loaded.
+    pub fn is_modified(&self) -> bool {
+        self.modified
+    }
+
+    ///Loads a todo file, upgrading files written in older formats. ".md" and ".json" files are imported from those formats.
+    pub fn load(file_name: &PathBuf) -> Result<Core, Error> {
+        match FileKind::of(file_name) {
+            Some(FileKind::Markdown) => {
+                let name = file_name.file_stem().unwrap_or_default().to_string_lossy();
+                Core::from_markdown(&fs::read_to_string(file_name)?, &name)
+            },
+            Some(FileKind::Json) => Core::from_json(&fs::read_to_string(file_name)?),
+            _ => {
+                let bytes = fs::read(file_name)?;
+                let (header, tree) = read_tree(&bytes)?;
+
+                let mut core = Core::from_tree(tree);
+                core.set_flags(header.flags);
+                Ok(core)
+            },
+        }
+    }
+
+    ///Writes the whole list as json with each entry holding a list of its children and its state.
+    pub fn to_json(&self) -> Result<String, Error> {
+        Ok(serde_json::to_string_pretty(&self.state_branch(self.tree_root())?)?)
+    }
+
+    ///Builds a list from json in the layout written by to_json.
+    pub fn from_json(text: &str) -> Result<Core, Error> {
+        let root: Branch = serde_json::from_str(text)?;
+        let mut core = Core::from_tree(Tree::new_with_root(root.entry));
+
+        for (i, child) in root.children.iter().enumerate() {
+            core.insert_branch(child, &[i])?;
+        }
+
+        Ok(core)
+    }
+
+    fn from_tree(tree: Tree<Entry>) -> Core {
+        Core{
             depth: 2,
             roots: vec![tree.get_root().unwrap()],
             tree: tree,
-        })
+            history: History::new(HISTORY_LIMIT),
+            modified: false,
+            filter: None,
+            hide_completed: false,
+        }
     }
 
-    pub fn delete(&mut self, id: usize) {
-        self.tree.remove(id).unwrap();
+    pub fn delete(&mut self, id: usize) -> Result<(), Error> {
+        if self.parent_of(id)?.is_none() { return Err(Error::Root) }
+
+        let command = Command::Remove{
+            path: self.path_of(id)?,
+            branch: self.branch(id)?,
+        };
+
+        self.tree.remove(id).map_err(|_| Error::InvalidId(id))?;
+        self.record(command);
+        self.drop_removed_roots();
+
+        Ok(())
+    }
+
+    ///Unfocuses entries that are no longer in the tree, falling back to the root of the tree.
+    fn drop_removed_roots(&mut self) {
+        let tree = &self.tree;
+        self.roots.retain(|&root| tree.data_at(root).is_ok());
+        if self.roots.is_empty() {
+            self.roots.push(self.tree_root());
+        }
     }
 
     pub fn current_root(&self) -> usize {
         *self.roots.last().unwrap()
     }
 
-    pub fn copy_entry(&mut self, cloning: usize, in_position: Position, node: usize) {
-        self.tree.clone_to(cloning, in_position, node).unwrap();
+    ///Focuses the list on the given entry so only its sub tree is shown.
+    pub fn push_root(&mut self, id: usize) -> Result<(), Error> {
+        self.get_entry(id)?;
+
+        if id != self.current_root() {
+            self.roots.push(id);
+        }
+
+        Ok(())
+    }
+
+    ///Returns focus to the previous root. Returns the id of the root that was left if there was one.
+    pub fn pop_root(&mut self) -> Option<usize> {
+        if self.roots.len() > 1 {
+            self.roots.pop()
+        }
+        else {
+            None
+        }
+    }
+
+    ///Returns the ids of every entry from the root of the tree down to the current root.
+    pub fn root_path(&self) -> Result<Vec<usize>, Error> {
+        self.ancestors(self.current_root())
+    }
+
+    ///Returns the ids of every entry from the root of the tree down to the given entry.
+    pub fn ancestors(&self, id: usize) -> Result<Vec<usize>, Error> {
+        let mut path = vec![id];
+
+        while let Some(parent) = self.parent_of(*path.last().unwrap())? {
+            path.push(parent);
+        }
+
+        path.reverse();
+        Ok(path)
+    }
+
+    ///Changes the focus and folds so the entry is shown in the list. Only the entries it is under are expanded.
+    ///Revealing is part of moving around the list so it does not count as a change.
+    pub fn reveal(&mut self, id: usize) -> Result<(), Error> {
+        let path = self.ancestors(id)?;
+
+        //the root of the tree is always in the path so this stops there at the latest.
+        while !path.contains(&self.current_root()) {
+            self.roots.pop();
+        }
+
+        let root_depth = path.iter().position(|&node| node == self.current_root()).unwrap();
+
+        for (depth, &ancestor) in path[root_depth..path.len() - 1].iter().enumerate() {
+            if !self.is_expanded(ancestor, depth)? {
+                self.get_entry_mut(ancestor)?.fold = Fold::Expanded;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn copy_entry(&mut self, cloning: usize, in_position: Position, node: usize) -> Result<(), Error> {
+        self.get_entry(cloning)?;
+        self.get_entry(node)?;
+        self.tree.clone_to(cloning, in_position, node).map_err(|_| Error::InvalidPosition)?;
+
+        let path = self.inserted_path(in_position, node)?;
+        let command = Command::Insert{
+            branch: self.branch(self.id_at(&path)?)?,
+            path: path,
+        };
+        self.record(command);
+
+        Ok(())
+    }
+
+    pub fn move_entry(&mut self, cloning: usize, in_position: Position, node: usize) -> Result<(), Error> {
+        let from = self.path_of(cloning)?;
+        self.get_entry(node)?;
+        self.tree.move_to(cloning, in_position, node).map_err(|_| Error::InvalidPosition)?;
+
+        let to = self.inserted_path(in_position, node)?;
+        self.record(Command::Move{
+            from: from,
+            to: to,
+        });
+
+        Ok(())
+    }
+
+    pub fn descendants_of(&self, id: usize) -> Result<Vec<usize>, Error> {
+        self.tree.descendants_of(id).map_err(|_| Error::InvalidId(id))
+    }
+
+    ///Reverts the last change to the list. Returns the id of the entry that was changed.
+    pub fn undo(&mut self) -> Result<Option<usize>, Error> {
+        match self.history.undo() {
+            Some(command) => Ok(Some(self.apply(command.inverse())?)),
+            None => Ok(None),
+        }
+    }
+
+    ///Applies the last undone change again. Returns the id of the entry that was changed.
+    pub fn redo(&mut self) -> Result<Option<usize>, Error> {
+        match self.history.redo() {
+            Some(command) => Ok(Some(self.apply(command)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn apply(&mut self, command: Command) -> Result<usize, Error> {
+        use Command::*;
+
+        self.modified = true;
+
+        let id = match command {
+            Insert{path, branch} => self.insert_branch(&branch, &path)?,
+            Remove{path, ..} => {
+                let id = self.id_at(&path)?;
+                self.tree.remove(id).map_err(|_| Error::InvalidId(id))?;
+                self.id_at(&path[..path.len() - 1])?
+            }
+            Move{from, to} => {
+                let id = self.id_at(&from)?;
+                let branch = self.branch(id)?;
+
+                self.tree.remove(id).map_err(|_| Error::InvalidId(id))?;
+                self.insert_branch(&branch, &to)?
+            }
+            Edit{path, after, ..} => {
+                let id = self.id_at(&path)?;
+                let entry = self.get_entry_mut(id)?;
+
+                //folds are not undone so the current one is kept.
+                let fold = entry.fold;
+                *entry = after;
+                entry.fold = fold;
+                id
+            }
+            Complete{path, after, ..} => {
+                for (id, complete) in self.pre_order()?.into_iter().zip(after) {
+                    self.get_entry_mut(id)?.complete = complete;
+                }
+                self.id_at(&path)?
+            }
+            Reorder{path, after, ..} => {
+                let id = self.id_at(&path)?;
+
+                for child in self.children_of(id)? {
+                    self.tree.remove(child).map_err(|_| Error::InvalidId(child))?;
+                }
+
+                for (i, branch) in after.iter().enumerate() {
+                    let mut child_path = path.clone();
+                    child_path.push(i);
+                    self.insert_branch(branch, &child_path)?;
+                }
+                id
+            }
+            Group(commands) => {
+                let mut id = self.current_root();
+                for command in commands {
+                    id = self.apply(command)?;
+                }
+                id
+            }
+        };
+
+        //focused entries may have been removed by the change.
+        self.drop_removed_roots();
+
+        Ok(id)
+    }
+
+    ///Changes the entry in place and records it so it can be undone. Nothing is recorded if the entry is unchanged.
+    fn edit<F: FnOnce(&mut Entry)>(&mut self, id: usize, change: F) -> Result<(), Error> {
+        let before = self.get_entry(id)?.clone();
+        change(self.get_entry_mut(id)?);
+
+        let after = self.get_entry(id)?.clone();
+        if before == after { return Ok(()) }
+
+        let command = Command::Edit{
+            path: self.path_of(id)?,
+            before: before,
+            after: after,
+        };
+        self.record(command);
+
+        Ok(())
+    }
+
+    fn record(&mut self, command: Command) {
+        self.modified = true;
+        self.history.record(command);
+    }
+
+    ///Records a change as part of the last one so they are undone together.
+    fn join(&mut self, command: Command) {
+        self.modified = true;
+        self.history.join(command);
+    }
+
+    fn record_completion(&mut self, id: usize, before: Vec<bool>) -> Result<(), Error> {
+        let after = self.completion()?;
+
+        if before != after {
+            let command = Command::Complete{
+                path: self.path_of(id)?,
+                before: before,
+                after: after,
+            };
+            self.record(command);
+        }
+
+        Ok(())
     }
 
-    pub fn move_entry(&mut self, cloning: usize, in_position: Position, node: usize) {
-        self.tree.move_to(cloning, in_position, node).unwrap();
+    ///Ids of every entry in the tree in pre order.
+    pub fn pre_order(&self) -> Result<Vec<usize>, Error> {
+        let mut ids = Vec::new();
+        let mut stack = vec![self.tree_root()];
+
+        while let Some(id) = stack.pop() {
+            ids.push(id);
+
+            let mut children = self.children_of(id)?;
+            children.reverse();
+            stack.append(&mut children);
+        }
+
+        Ok(ids)
+    }
+
+    ///View settings kept in the header of todo files.
+    fn flags(&self) -> u16 {
+        let mut flags = 0;
+
+        if self.hide_completed {
+            flags |= FLAG_HIDE_COMPLETED;
+        }
+
+        flags
+    }
+
+    fn set_flags(&mut self, flags: u16) {
+        self.hide_completed = flags & FLAG_HIDE_COMPLETED != 0;
+    }
+
+    fn completion(&self) -> Result<Vec<bool>, Error> {
+        self.pre_order()?.into_iter().map(|id| Ok(self.get_entry(id)?.complete)).collect()
+    }
+
+    fn child_at(&self, id: usize, index: usize) -> Result<usize, Error> {
+        self.children_of(id)?.get(index).copied().ok_or(Error::InvalidPosition)
+    }
+
+    ///Path of child indexes leading from the root of the tree to the entry.
+    pub fn path_of(&self, id: usize) -> Result<Vec<usize>, Error> {
+        let mut path = Vec::new();
+        let mut current = id;
+
+        while let Some(parent) = self.parent_of(current)? {
+            path.push(self.children_of(parent)?.into_iter().position(|child| child == current).unwrap());
+            current = parent;
+        }
+
+        path.reverse();
+        Ok(path)
     }
 
-    pub fn descendants_of(&self, id: usize) -> Vec<usize> {
-        self.tree.descendants_of(id).unwrap()
+    ///Id of the entry at the end of a path of child indexes. An empty path is the root of the tree.
+    pub fn id_at(&self, path: &[usize]) -> Result<usize, Error> {
+        let mut id = self.tree_root();
+
+        for index in path {
+            id = self.child_at(id, *index)?;
+        }
+
+        Ok(id)
+    }
+
+    ///Path of an entry that was just placed in the given position relative to node.
+    fn inserted_path(&self, pos: Position, node: usize) -> Result<Vec<usize>, Error> {
+        let mut path = self.path_of(node)?;
+
+        match pos {
+            Position::FirstChild    => path.push(0),
+            Position::LastChild     => path.push(self.children_of(node)?.len() - 1),
+            Position::SiblingBefore => *path.last_mut().ok_or(Error::Root)? -= 1,
+            Position::SiblingAfter  => *path.last_mut().ok_or(Error::Root)? += 1,
+        }
+
+        Ok(path)
+    }
+
+    fn branch(&self, id: usize) -> Result<Branch, Error> {
+        Ok(Branch{
+            entry: self.get_entry(id)?.clone(),
+            children: self.children_of(id)?.into_iter().map(|child| self.branch(child)).collect::<Result<_, _>>()?,
+        })
+    }
+
+    ///Rebuilds a branch so its top entry ends up at the given path. Returns the id of the top entry.
+    fn insert_branch(&mut self, branch: &Branch, path: &[usize]) -> Result<usize, Error> {
+        let (index, parent_path) = path.split_last().ok_or(Error::Root)?;
+        let parent = self.id_at(parent_path)?;
+
+        if *index == 0 {
+            self.tree.new_node(branch.entry.clone(), Position::FirstChild, parent).map_err(|_| Error::InvalidId(parent))?;
+        }
+        else {
+            let before = self.child_at(parent, index - 1)?;
+            self.tree.new_node(branch.entry.clone(), Position::SiblingAfter, before).map_err(|_| Error::InvalidId(before))?;
+        }
+
+        for (i, child) in branch.children.iter().enumerate() {
+            let mut child_path = path.to_vec();
+            child_path.push(i);
+            self.insert_branch(child, &child_path)?;
+        }
+
+        self.id_at(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ///Every entry in pre order indented by its depth and marked if complete.
+    fn outline(core: &Core) -> Vec<String> {
+        core.pre_order().unwrap().into_iter().map(|id| {
+            let entry = core.get_entry(id).unwrap();
+            format!("{}{}{}", "-".repeat(core.path_of(id).unwrap().len()), entry.name, if entry.complete { " x" } else { "" })
+        }).collect()
+    }
+
+    ///Undoes and redoes the last change, checking the list before and after it.
+    fn undo_redo(core: &mut Core, before: &[&str], after: &[&str]) {
+        assert_eq!(outline(core), after);
+
+        core.undo().unwrap();
+        assert_eq!(outline(core), before);
+
+        core.redo().unwrap();
+        assert_eq!(outline(core), after);
+    }
+
+    ///A list with "a" holding "a1" and "a2", followed by "b" and "c".
+    fn list() -> Core {
+        let mut core = Core::new(String::from("list"));
+        let root = core.current_root();
+
+        let a = core.new_entry(String::from("a"), Position::LastChild, root).unwrap();
+        core.new_entry(String::from("a1"), Position::LastChild, a).unwrap();
+        core.new_entry(String::from("a2"), Position::LastChild, a).unwrap();
+        core.new_entry(String::from("b"), Position::LastChild, root).unwrap();
+        core.new_entry(String::from("c"), Position::LastChild, root).unwrap();
+
+        core
+    }
+
+    fn child(core: &Core, path: &[usize]) -> usize {
+        core.id_at(path).unwrap()
+    }
+
+    const LIST: [&str; 6] = ["list", "-a", "--a1", "--a2", "-b", "-c"];
+
+    #[test]
+    fn insert() {
+        let mut core = list();
+        core.new_entry(String::from("b1"), Position::FirstChild, child(&core, &[1])).unwrap();
+
+        undo_redo(&mut core, &LIST, &["list", "-a", "--a1", "--a2", "-b", "--b1", "-c"]);
+    }
+
+    #[test]
+    fn remove_sub_tree() {
+        let mut core = list();
+        core.delete(child(&core, &[0])).unwrap();
+
+        undo_redo(&mut core, &LIST, &["list", "-b", "-c"]);
+    }
+
+    #[test]
+    fn move_between_siblings() {
+        let mut core = list();
+        core.move_entry(child(&core, &[0]), Position::SiblingAfter, child(&core, &[1])).unwrap();
+
+        undo_redo(&mut core, &LIST, &["list", "-b", "-a", "--a1", "--a2", "-c"]);
+    }
+
+    #[test]
+    fn move_to_other_parent() {
+        let mut core = list();
+        core.move_entry(child(&core, &[2]), Position::SiblingBefore, child(&core, &[0, 1])).unwrap();
+
+        undo_redo(&mut core, &LIST, &["list", "-a", "--a1", "--c", "--a2", "-b"]);
+    }
+
+    #[test]
+    fn edit() {
+        let mut core = list();
+        core.rename(child(&core, &[1]), String::from("renamed")).unwrap();
+
+        undo_redo(&mut core, &LIST, &["list", "-a", "--a1", "--a2", "-renamed", "-c"]);
+    }
+
+    #[test]
+    fn complete() {
+        let mut core = list();
+        core.set_complete(child(&core, &[0, 0])).unwrap();
+        core.set_complete(child(&core, &[0, 1])).unwrap();
+
+        //completing the last child completes the parent too.
+        undo_redo(&mut core, &["list", "-a", "--a1 x", "--a2", "-b", "-c"], &["list", "-a x", "--a1 x", "--a2 x", "-b", "-c"]);
+    }
+
+    #[test]
+    fn reorder() {
+        let mut core = list();
+        core.sort_children(core.current_root(), SortKey::Name, false).unwrap();
+        core.rename(child(&core, &[0]), String::from("d")).unwrap();
+        core.sort_children(core.current_root(), SortKey::Name, false).unwrap();
+
+        undo_redo(&mut core, &["list", "-d", "--a1", "--a2", "-b", "-c"], &["list", "-b", "-c", "-d", "--a1", "--a2"]);
+    }
+
+    #[test]
+    fn undo_without_history() {
+        let mut core = list();
+
+        for _ in 0..5 {
+            core.undo().unwrap();
+        }
+
+        assert_eq!(outline(&core), ["list"]);
+        assert!(core.undo().unwrap().is_none());
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let mut core = list();
+        core.set_complete(child(&core, &[0, 0])).unwrap();
+        core.set_due(child(&core, &[0, 1]), Due::parse("2024-05-01 17:30")).unwrap();
+        core.set_notes(child(&core, &[1]), String::from("Some notes")).unwrap();
+
+        let text = core.to_json().unwrap();
+        assert!(text.contains(r#""due": "2024-05-01 17:30""#));
+        assert!(text.contains(r#""state": "partial""#));
+
+        //the state is only written for readers of the export and is left out on import.
+        let imported = Core::from_json(&text).unwrap();
+        assert_eq!(outline(&imported), ["list", "-a", "--a1 x", "--a2", "-b", "-c"]);
+        assert_eq!(imported.get_entry(child(&imported, &[0, 1])).unwrap().due, Due::parse("2024-05-01 17:30"));
+        assert_eq!(imported.get_entry(child(&imported, &[1])).unwrap().notes, "Some notes");
+        assert_eq!(imported.to_json().unwrap(), text);
+    }
+
+    #[test]
+    fn json_only_names() {
+        let core = Core::from_json(r#"{"name": "list", "children": [{"name": "a", "children": [{"name": "a1"}]}, {"name": "b", "complete": true}]}"#).unwrap();
+        assert_eq!(outline(&core), ["list", "-a", "--a1", "-b x"]);
+    }
+
+    #[test]
+    fn delete_focused() {
+        let mut core = list();
+        let a = child(&core, &[0]);
+        let a1 = child(&core, &[0, 0]);
+
+        core.push_root(a).unwrap();
+        core.push_root(a1).unwrap();
+        core.delete(a).unwrap();
+
+        assert_eq!(core.current_root(), core.tree_root());
+        assert!(core.get_entries_info().is_ok());
     }
 }
@@ -0,0 +1,168 @@
+use super::*;
+
+///Indent used for each level of the list.
+const INDENT: &str = "  ";
+
+impl Core {
+    ///Writes the whole list as a nested GitHub task list with the root entry as the title.
+    pub fn to_markdown(&self) -> Result<String, Error> {
+        let mut text = format!("# {}\n\n", self.name());
+
+        for child in self.children_of(self.tree_root())? {
+            self.write_markdown(child, 0, &mut text)?;
+        }
+
+        Ok(text)
+    }
+
+    fn write_markdown(&self, id: usize, depth: usize, text: &mut String) -> Result<(), Error> {
+        let entry = self.get_entry(id)?;
+
+        text.push_str(&format!("{}- [{}] {}\n",
+            INDENT.repeat(depth),
+            if entry.complete { 'x' } else { ' ' },
+            entry.name,
+        ));
+
+        //notes are quoted so lines that look like items or headings are read back as notes.
+        for line in entry.notes.lines() {
+            let quoted = if line.is_empty() { String::from(">") } else { format!("> {}", line) };
+            text.push_str(&format!("{}{}\n", INDENT.repeat(depth + 1), quoted));
+        }
+
+        for child in self.children_of(id)? {
+            self.write_markdown(child, depth + 1, text)?;
+        }
+
+        Ok(())
+    }
+
+    ///Builds a list out of a markdown task list.
+    ///The first heading before any items names the list, otherwise the given name is used.
+    ///Items are nested by their indent. Other lines indented under an item are its notes and the rest are skipped.
+    ///Quoted lines under an item are always notes, even if they look like items or headings.
+    pub fn from_markdown(text: &str, name: &str) -> Result<Core, Error> {
+        let mut tree = Tree::new_with_root(Entry::new(String::from(name), false));
+        let root = tree.get_root().unwrap();
+
+        //indent and id of the items that later items can be nested under.
+        let mut parents: Vec<(usize, usize)> = Vec::new();
+
+        for line in text.lines() {
+            let trimmed = line.trim_start();
+
+            if let Some(&(parent_indent, id)) = parents.last() {
+                if let Some(quote) = trimmed.strip_prefix('>') {
+                    if indent_width(line) > parent_indent {
+                        let note = quote.strip_prefix(' ').unwrap_or(quote).trim_end();
+                        add_note(&mut tree, id, note)?;
+                        continue
+                    }
+                }
+            }
+
+            if let Some(title) = trimmed.strip_prefix("# ") {
+                if parents.is_empty() {
+                    tree.data_at_mut(root).map_err(|_| Error::InvalidId(root))?.name = String::from(title.trim());
+                }
+                continue
+            }
+
+            if let Some((complete, name)) = parse_item(trimmed) {
+                let indent = indent_width(line);
+
+                while let Some(&(parent_indent, _)) = parents.last() {
+                    if parent_indent < indent { break }
+                    parents.pop();
+                }
+
+                let parent = parents.last().map(|&(_, id)| id).unwrap_or(root);
+                let entry = Entry::new(String::from(name), complete);
+
+                tree.new_node(entry, Position::LastChild, parent).map_err(|_| Error::InvalidId(parent))?;
+                let id = tree.children_of(parent).map_err(|_| Error::InvalidId(parent))?.into_iter().last().unwrap();
+
+                parents.push((indent, id));
+            }
+            else if let Some(&(parent_indent, id)) = parents.last() {
+                if trimmed.is_empty() || indent_width(line) <= parent_indent { continue }
+
+                add_note(&mut tree, id, trimmed.trim_end())?;
+            }
+        }
+
+        Ok(Core::from_tree(tree))
+    }
+}
+
+///Adds a line to the notes of the entry.
+fn add_note(tree: &mut Tree<Entry>, id: usize, line: &str) -> Result<(), Error> {
+    let entry = tree.data_at_mut(id).map_err(|_| Error::InvalidId(id))?;
+    if entry.has_notes() {
+        entry.notes.push('\n');
+    }
+    entry.notes.push_str(line);
+
+    Ok(())
+}
+
+///Splits a list item into its completion and text. Items without a check box or checked with "-" are incomplete.
+fn parse_item(line: &str) -> Option<(bool, &str)> {
+    let item = line.strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))
+        .or_else(|| line.strip_prefix("+ "))?;
+
+    if let Some(name) = item.strip_prefix("[ ] ").or_else(|| item.strip_prefix("[-] ")) {
+        Some((false, name.trim()))
+    }
+    else if let Some(name) = item.strip_prefix("[x] ").or_else(|| item.strip_prefix("[X] ")) {
+        Some((true, name.trim()))
+    }
+    else {
+        Some((false, item.trim()))
+    }
+}
+
+///Width of the leading white space of a line with tabs counting as four spaces.
+fn indent_width(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let text = "# Release\n\n- [x] Build\n  - [x] Linux\n- [ ] Publish\n  - [x] Tag\n- [ ] Announce\n  > Run the release script.\n  > - Tag first\n  > # Not a title\n  - [ ] Notes\n    - [ ] Draft\n";
+
+        let core = Core::from_markdown(text, "file").unwrap();
+
+        assert_eq!(core.name(), "Release");
+        assert_eq!(core.to_markdown().unwrap(), text);
+
+        let announce = core.id_at(&[2]).unwrap();
+        assert_eq!(core.get_entry(announce).unwrap().notes, "Run the release script.\n- Tag first\n# Not a title");
+        assert_eq!(core.children_of(announce).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn plain_notes() {
+        let core = Core::from_markdown("- [ ] a\n  First line.\n  > Second line.\n", "file").unwrap();
+        let a = core.id_at(&[0]).unwrap();
+
+        assert_eq!(core.get_entry(a).unwrap().notes, "First line.\nSecond line.");
+    }
+
+    #[test]
+    fn items() {
+        assert_eq!(parse_item("- [ ] a"), Some((false, "a")));
+        assert_eq!(parse_item("* [X] b"), Some((true, "b")));
+        assert_eq!(parse_item("+ c"), Some((false, "c")));
+        assert_eq!(parse_item("- [-] d"), Some((false, "d")));
+        assert_eq!(parse_item("text"), None);
+    }
+}
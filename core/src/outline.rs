@@ -0,0 +1,185 @@
+use super::*;
+
+//Single step moves of an entry within the current root. Moves that are not possible leave the list as it is.
+impl Core {
+    ///Swaps the entry with the sibling before it.
+    pub fn move_up(&mut self, id: usize) -> Result<(), Error> {
+        if let Some((siblings, index)) = self.siblings(id)? {
+            if index > 0 {
+                self.move_entry(id, Position::SiblingBefore, siblings[index - 1])?;
+            }
+        }
+
+        Ok(())
+    }
+
+    ///Swaps the entry with the sibling after it.
+    pub fn move_down(&mut self, id: usize) -> Result<(), Error> {
+        if let Some((siblings, index)) = self.siblings(id)? {
+            if index + 1 < siblings.len() {
+                self.move_entry(id, Position::SiblingAfter, siblings[index + 1])?;
+            }
+        }
+
+        Ok(())
+    }
+
+    ///Makes the entry the last child of the sibling before it. Returns the new parent if the entry was moved.
+    pub fn indent(&mut self, id: usize) -> Result<Option<usize>, Error> {
+        if let Some((siblings, index)) = self.siblings(id)? {
+            if index > 0 {
+                let parent = siblings[index - 1];
+                self.move_to_parent(id, Position::LastChild, parent)?;
+
+                return Ok(Some(parent))
+            }
+        }
+
+        Ok(None)
+    }
+
+    ///Makes the entry the sibling after its parent. Entries directly under the current root stay where they are.
+    pub fn outdent(&mut self, id: usize) -> Result<(), Error> {
+        if id == self.current_root() { return Ok(()) }
+
+        if let Some(parent) = self.parent_of(id)? {
+            if parent != self.current_root() {
+                self.move_to_parent(id, Position::SiblingAfter, parent)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    ///Moves the entry to another parent and updates the completion of the parents it left and joined.
+    ///Both are undone in one step.
+    fn move_to_parent(&mut self, id: usize, position: Position, node: usize) -> Result<(), Error> {
+        let old_parent = self.parent_of(id)?;
+        self.move_entry(id, position, node)?;
+
+        let before = self.completion()?;
+        let new_parent = self.parent_of(id)?;
+        self.complete_up(new_parent)?;
+        self.complete_up(old_parent)?;
+
+        let after = self.completion()?;
+        if before != after {
+            let command = Command::Complete{
+                path: self.path_of(id)?,
+                before: before,
+                after: after,
+            };
+            self.join(command);
+        }
+
+        Ok(())
+    }
+
+    ///Completes the entry and its ancestors if all of their children are complete and reopens them if not.
+    ///Entries without children keep their completion.
+    fn complete_up(&mut self, mut parent: Option<usize>) -> Result<(), Error> {
+        while let Some(id) = parent {
+            let children = self.children_of(id)?;
+            if children.is_empty() { return Ok(()) }
+
+            let mut complete = true;
+            for child in children {
+                complete &= self.get_entry(child)?.complete;
+            }
+
+            let entry = self.get_entry_mut(id)?;
+            if entry.complete == complete { return Ok(()) }
+            entry.complete = complete;
+
+            parent = self.parent_of(id)?;
+        }
+
+        Ok(())
+    }
+
+    ///The entry with its siblings and where it is among them. None for the current root, which is never moved.
+    fn siblings(&self, id: usize) -> Result<Option<(Vec<usize>, usize)>, Error> {
+        if id == self.current_root() { return Ok(None) }
+
+        let parent = match self.parent_of(id)? {
+            Some(parent) => parent,
+            None => return Ok(None),
+        };
+
+        let siblings = self.children_of(parent)?;
+        let index = siblings.iter().position(|&sibling| sibling == id).unwrap();
+
+        Ok(Some((siblings, index)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(core: &Core, id: usize) -> Vec<String> {
+        core.children_of(id).unwrap().into_iter().map(|child| core.get_entry(child).unwrap().name.clone()).collect()
+    }
+
+    #[test]
+    fn moves() {
+        let mut core = Core::new(String::from("list"));
+        let root = core.current_root();
+
+        let a = core.new_entry(String::from("a"), Position::LastChild, root).unwrap();
+        let b = core.new_entry(String::from("b"), Position::LastChild, root).unwrap();
+        core.new_entry(String::from("c"), Position::LastChild, root).unwrap();
+
+        core.move_up(b).unwrap();
+        assert_eq!(names(&core, root), vec!["b", "a", "c"]);
+
+        core.move_up(b).unwrap();
+        assert_eq!(names(&core, root), vec!["b", "a", "c"]);
+
+        core.move_down(b).unwrap();
+        assert_eq!(names(&core, root), vec!["a", "b", "c"]);
+
+        assert_eq!(core.indent(b).unwrap(), Some(a));
+        assert_eq!(names(&core, root), vec!["a", "c"]);
+        assert_eq!(names(&core, a), vec!["b"]);
+
+        core.outdent(b).unwrap();
+        assert_eq!(names(&core, root), vec!["a", "b", "c"]);
+
+        core.outdent(b).unwrap();
+        assert_eq!(names(&core, root), vec!["a", "b", "c"]);
+
+        core.undo().unwrap();
+        assert_eq!(names(&core, a), vec!["b"]);
+    }
+
+    #[test]
+    fn completion() {
+        let mut core = Core::new(String::from("list"));
+        let root = core.current_root();
+
+        let a = core.new_entry(String::from("a"), Position::LastChild, root).unwrap();
+        let b = core.new_entry(String::from("b"), Position::LastChild, root).unwrap();
+        core.set_complete(a).unwrap();
+
+        //an open entry moved under a done one reopens it.
+        core.indent(b).unwrap();
+        assert_eq!(core.state(a).unwrap(), State::Incomplete);
+
+        core.undo().unwrap();
+        assert_eq!(names(&core, root), vec!["a", "b"]);
+        assert!(core.get_entry(core.id_at(&[0]).unwrap()).unwrap().complete);
+
+        core.redo().unwrap();
+        let a = core.id_at(&[0]).unwrap();
+        let b = core.id_at(&[0, 0]).unwrap();
+        assert!(!core.get_entry(a).unwrap().complete);
+
+        //the parent left behind is done once only done entries are left in it.
+        let c = core.new_entry(String::from("c"), Position::LastChild, a).unwrap();
+        core.set_complete(c).unwrap();
+        core.outdent(b).unwrap();
+        assert!(core.get_entry(a).unwrap().complete);
+        assert_eq!(names(&core, root), vec!["a", "b"]);
+    }
+}
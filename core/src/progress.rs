@@ -0,0 +1,90 @@
+use super::*;
+
+///How many of the entries below a parent are complete.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Progress {
+    pub done: usize,
+    pub total: usize,
+}
+
+impl Progress {
+    pub fn percent(&self) -> usize {
+        if self.total == 0 { return 100 }
+        self.done * 100 / self.total
+    }
+}
+
+///How progress is shown after the name of entries with children.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum ProgressStyle {
+    Off,
+    ///Like "[3/7]".
+    Count,
+    ///Like "43%".
+    Percent,
+}
+
+///Which entries progress is counted from.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum ProgressCount {
+    ///Every entry below the parent without children of its own.
+    Leaves,
+    ///Only the direct children of the parent.
+    Children,
+}
+
+impl Core {
+    ///Returns None if the entry has no children.
+    pub fn progress(&self, id: usize, count: ProgressCount) -> Result<Option<Progress>, Error> {
+        let children = self.children_of(id)?;
+        if children.is_empty() { return Ok(None) }
+
+        let counted = match count {
+            ProgressCount::Children => children,
+            ProgressCount::Leaves   => {
+                let mut leaves = Vec::new();
+
+                for descendant in self.descendants_of(id)? {
+                    if descendant != id && self.children_of(descendant)?.is_empty() {
+                        leaves.push(descendant);
+                    }
+                }
+
+                leaves
+            },
+        };
+
+        let mut done = 0;
+        for &counted_id in &counted {
+            if self.get_entry(counted_id)?.complete {
+                done += 1;
+            }
+        }
+
+        Ok(Some(Progress{
+            done: done,
+            total: counted.len(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count() {
+        let mut core = Core::new(String::from("list"));
+        let root = core.current_root();
+
+        let parent = core.new_entry(String::from("Parent"), Position::LastChild, root).unwrap();
+        let leaf = core.new_entry(String::from("Leaf"), Position::LastChild, parent).unwrap();
+        core.new_entry(String::from("Leaf 2"), Position::LastChild, parent).unwrap();
+        core.new_entry(String::from("Single"), Position::LastChild, root).unwrap();
+        core.set_complete(leaf).unwrap();
+
+        assert_eq!(core.progress(root, ProgressCount::Leaves).unwrap(), Some(Progress{done: 1, total: 3}));
+        assert_eq!(core.progress(root, ProgressCount::Children).unwrap(), Some(Progress{done: 0, total: 2}));
+        assert_eq!(core.progress(leaf, ProgressCount::Leaves).unwrap(), None);
+    }
+}
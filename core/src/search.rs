@@ -0,0 +1,57 @@
+use super::*;
+
+use regex::{Regex, RegexBuilder};
+
+///What entry names are searched for. Both kinds ignore case.
+pub enum Query {
+    Text(String),
+    Regex(Regex),
+}
+
+impl Query {
+    ///Text between slashes like "/fix(ed)?/" is a regular expression, anything else is found as is.
+    pub fn parse(text: &str) -> Result<Query, Error> {
+        if text.len() > 1 && text.starts_with('/') && text.ends_with('/') {
+            let regex = RegexBuilder::new(&text[1..text.len() - 1]).case_insensitive(true).build()?;
+            Ok(Query::Regex(regex))
+        }
+        else {
+            Ok(Query::Text(text.to_lowercase()))
+        }
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            Query::Text(text)   => name.to_lowercase().contains(text.as_str()),
+            Query::Regex(regex) => regex.is_match(name),
+        }
+    }
+}
+
+impl Core {
+    ///Every entry in the list with a name matching the query, in list order.
+    pub fn search(&self, query: &Query) -> Result<Vec<usize>, Error> {
+        let mut ids = Vec::new();
+
+        for id in self.pre_order()? {
+            if query.matches(&self.get_entry(id)?.name) {
+                ids.push(id);
+            }
+        }
+
+        Ok(ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches() {
+        assert!(Query::parse("LOG").unwrap().matches("Fix login"));
+        assert!(Query::parse("/^fix (log|sign)in$/").unwrap().matches("Fix Login"));
+        assert!(!Query::parse("/^login/").unwrap().matches("Fix login"));
+        assert!(Query::parse("/(/").is_err());
+    }
+}
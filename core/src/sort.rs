@@ -0,0 +1,147 @@
+use super::*;
+
+use std::cmp::Ordering;
+
+///What siblings are ordered by. Entries that are equal keep their order.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SortKey {
+    ///Highest priority first.
+    Priority,
+    ///Alphabetical ignoring case.
+    Name,
+    ///Incomplete entries first.
+    Completion,
+    ///Earliest due date first with entries without one last.
+    Due,
+}
+
+impl SortKey {
+    ///Reads the key from the start of its name so "p", "prio" and "priority" all work.
+    pub fn parse(text: &str) -> Option<SortKey> {
+        let text = text.trim().to_lowercase();
+        if text.is_empty() { return None }
+
+        [SortKey::Priority, SortKey::Name, SortKey::Completion, SortKey::Due].iter()
+            .copied()
+            .find(|key| key.name().starts_with(&text))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SortKey::Priority   => "priority",
+            SortKey::Name       => "name",
+            SortKey::Completion => "completion",
+            SortKey::Due        => "due",
+        }
+    }
+
+    fn compare(&self, a: &Entry, b: &Entry) -> Ordering {
+        match self {
+            SortKey::Priority   => b.priority.cmp(&a.priority),
+            SortKey::Name       => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            SortKey::Completion => a.complete.cmp(&b.complete),
+            SortKey::Due        => match (a.due, b.due) {
+                //entries without a time are due at the end of the day so they come after timed ones.
+                (Some(a), Some(b)) => (a.date, a.time.is_none(), a.time).cmp(&(b.date, b.time.is_none(), b.time)),
+                (Some(_), None)    => Ordering::Less,
+                (None, Some(_))    => Ordering::Greater,
+                (None, None)       => Ordering::Equal,
+            },
+        }
+    }
+}
+
+impl Core {
+    ///Reorders the children of the entry by the key. If recursive every level below them is sorted too.
+    ///Nothing is recorded if the order is unchanged.
+    pub fn sort_children(&mut self, id: usize, key: SortKey, recursive: bool) -> Result<(), Error> {
+        let before = self.children_of(id)?.into_iter().map(|child| self.branch(child)).collect::<Result<Vec<_>, _>>()?;
+
+        let mut after = before.clone();
+        sort_branches(&mut after, key, recursive);
+        if before == after { return Ok(()) }
+
+        let command = Command::Reorder{
+            path: self.path_of(id)?,
+            before: before,
+            after: after,
+        };
+        self.apply(command.clone())?;
+        self.record(command);
+
+        Ok(())
+    }
+}
+
+fn sort_branches(branches: &mut Vec<Branch>, key: SortKey, recursive: bool) {
+    branches.sort_by(|a, b| key.compare(&a.entry, &b.entry));
+
+    if recursive {
+        for branch in branches {
+            sort_branches(&mut branch.children, key, recursive);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(SortKey::parse("p"), Some(SortKey::Priority));
+        assert_eq!(SortKey::parse("Name"), Some(SortKey::Name));
+        assert_eq!(SortKey::parse("due"), Some(SortKey::Due));
+        assert_eq!(SortKey::parse("x"), None);
+        assert_eq!(SortKey::parse(""), None);
+    }
+
+    #[test]
+    fn sort_by_name() {
+        let mut core = Core::new(String::from("list"));
+        let root = core.current_root();
+
+        for name in &["b", "C", "a"] {
+            core.new_entry(String::from(*name), Position::LastChild, root).unwrap();
+        }
+
+        core.sort_children(root, SortKey::Name, false).unwrap();
+        let names: Vec<String> = core.children_of(root).unwrap().into_iter().map(|id| core.get_entry(id).unwrap().name.clone()).collect();
+        assert_eq!(names, vec!["a", "b", "C"]);
+
+        core.undo().unwrap();
+        let names: Vec<String> = core.children_of(root).unwrap().into_iter().map(|id| core.get_entry(id).unwrap().name.clone()).collect();
+        assert_eq!(names, vec!["b", "C", "a"]);
+    }
+
+    #[test]
+    fn sort_by_due() {
+        let mut core = Core::new(String::from("list"));
+        let root = core.current_root();
+
+        for (name, due) in &[("none", None), ("day", Some("2024-05-01")), ("evening", Some("2024-05-01 18:00")), ("morning", Some("2024-05-01 09:00"))] {
+            let id = core.new_entry(String::from(*name), Position::LastChild, root).unwrap();
+            core.set_due(id, due.and_then(Due::parse)).unwrap();
+        }
+
+        core.sort_children(root, SortKey::Due, false).unwrap();
+        let names: Vec<String> = core.children_of(root).unwrap().into_iter().map(|id| core.get_entry(id).unwrap().name.clone()).collect();
+        assert_eq!(names, vec!["morning", "evening", "day", "none"]);
+    }
+
+    #[test]
+    fn unchanged_order() {
+        let mut core = Core::new(String::from("list"));
+        let root = core.current_root();
+
+        let a = core.new_entry(String::from("a"), Position::LastChild, root).unwrap();
+        core.new_entry(String::from("b"), Position::LastChild, root).unwrap();
+
+        core.sort_children(root, SortKey::Name, false).unwrap();
+        core.sort_children(a, SortKey::Name, false).unwrap();
+
+        //the last change is still adding "b".
+        core.undo().unwrap();
+        assert_eq!(core.children_of(root).unwrap(), vec![a]);
+    }
+}
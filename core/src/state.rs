@@ -0,0 +1,65 @@
+use super::*;
+
+///How far along an entry is. Partial entries are incomplete but something below them is done.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum State {
+    Incomplete,
+    Partial,
+    Complete,
+}
+
+///A branch written to json with the state of each entry next to its fields.
+#[derive(Serialize)]
+pub(crate) struct StateBranch<'a> {
+    #[serde(flatten)]
+    entry: &'a Entry,
+    state: State,
+    children: Vec<StateBranch<'a>>,
+}
+
+impl Core {
+    pub fn state(&self, id: usize) -> Result<State, Error> {
+        if self.get_entry(id)?.complete { return Ok(State::Complete) }
+
+        for descendant in self.descendants_of(id)? {
+            if descendant != id && self.get_entry(descendant)?.complete {
+                return Ok(State::Partial)
+            }
+        }
+
+        Ok(State::Incomplete)
+    }
+
+    pub(crate) fn state_branch(&self, id: usize) -> Result<StateBranch, Error> {
+        Ok(StateBranch{
+            entry: self.get_entry(id)?,
+            state: self.state(id)?,
+            children: self.children_of(id)?.into_iter().map(|child| self.state_branch(child)).collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partial() {
+        let mut core = Core::new(String::from("list"));
+        let root = core.current_root();
+
+        let parent = core.new_entry(String::from("Parent"), Position::LastChild, root).unwrap();
+        let child = core.new_entry(String::from("Child"), Position::LastChild, parent).unwrap();
+        let grandchild = core.new_entry(String::from("Grandchild"), Position::LastChild, child).unwrap();
+        core.new_entry(String::from("Child 2"), Position::LastChild, parent).unwrap();
+
+        assert_eq!(core.state(parent).unwrap(), State::Incomplete);
+
+        core.set_complete(grandchild).unwrap();
+        assert_eq!(core.state(grandchild).unwrap(), State::Complete);
+        assert_eq!(core.state(child).unwrap(), State::Complete);
+        assert_eq!(core.state(parent).unwrap(), State::Partial);
+        assert_eq!(core.state(root).unwrap(), State::Partial);
+    }
+}
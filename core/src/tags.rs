@@ -0,0 +1,83 @@
+use super::*;
+
+use std::collections::BTreeMap;
+
+impl Entry {
+    ///Words in the name starting with "#", without the "#" and any punctuation after them.
+    pub fn tags(&self) -> Vec<&str> {
+        self.name.split_whitespace()
+            .filter_map(|word| word.strip_prefix('#'))
+            .map(|tag| tag.trim_end_matches(|c: char| c.is_ascii_punctuation()))
+            .filter(|tag| !tag.is_empty())
+            .collect()
+    }
+
+    ///Tags are matched ignoring case and with or without the leading "#".
+    pub fn has_tag(&self, tag: &str) -> bool {
+        let tag = tag.trim_start_matches('#');
+        let tag = tag.to_lowercase();
+        self.tags().iter().any(|own| own.to_lowercase() == tag)
+    }
+}
+
+impl Core {
+    ///Every tag used in the list in lower case with how many entries have it, in alphabetical order.
+    pub fn tags(&self) -> Result<Vec<(String, usize)>, Error> {
+        let mut counts = BTreeMap::new();
+
+        for id in self.pre_order()? {
+            let mut tags: Vec<String> = self.get_entry(id)?.tags().iter().map(|tag| tag.to_lowercase()).collect();
+            tags.sort();
+            tags.dedup();
+
+            for tag in tags {
+                *counts.entry(tag).or_insert(0) += 1;
+            }
+        }
+
+        Ok(counts.into_iter().collect())
+    }
+
+    ///Only shows entries with the tag and the entries they are under. None shows every entry again.
+    pub fn set_filter(&mut self, tag: Option<String>) {
+        self.filter = tag.map(|tag| String::from(tag.trim_start_matches('#')));
+    }
+
+    pub fn filter(&self) -> Option<&str> {
+        self.filter.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags() {
+        let entry = Entry::new(String::from("Fix login #backend, #Alice #"), false);
+
+        assert_eq!(entry.tags(), vec!["backend", "Alice"]);
+        assert!(entry.has_tag("#alice"));
+        assert!(!entry.has_tag("login"));
+
+        //tags are matched with the same case folding as the tag index.
+        let entry = Entry::new(String::from("Complain #Ärger"), false);
+        assert!(entry.has_tag("ärger"));
+    }
+
+    #[test]
+    fn filter() {
+        let mut core = Core::new(String::from("list"));
+        let root = core.current_root();
+
+        let area = core.new_entry(String::from("Area"), Position::LastChild, root).unwrap();
+        let task = core.new_entry(String::from("Task #bob"), Position::LastChild, area).unwrap();
+        core.new_entry(String::from("Other"), Position::LastChild, root).unwrap();
+
+        assert_eq!(core.tags().unwrap(), vec![(String::from("bob"), 1)]);
+
+        core.set_filter(Some(String::from("#bob")));
+        let ids: Vec<usize> = core.get_entries_info().unwrap().iter().map(|info| info.id).collect();
+        assert_eq!(ids, vec![root, area, task]);
+    }
+}
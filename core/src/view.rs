@@ -0,0 +1,268 @@
+use super::*;
+
+///Whether the children of an entry are shown.
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Fold {
+    ///Shown if the entry is above the depth of the list.
+    Depth,
+    Collapsed,
+    Expanded,
+}
+
+impl Fold {
+    pub fn is_depth(&self) -> bool {
+        *self == Fold::Depth
+    }
+
+    pub(crate) fn to_byte(self) -> u8 {
+        self as u8
+    }
+
+    pub(crate) fn from_byte(byte: u8) -> Option<Fold> {
+        match byte {
+            0 => Some(Fold::Depth),
+            1 => Some(Fold::Collapsed),
+            2 => Some(Fold::Expanded),
+            _ => None,
+        }
+    }
+}
+
+impl Default for Fold {
+    fn default() -> Self {
+        Fold::Depth
+    }
+}
+
+//Decides which entries below the current root are shown.
+//Without a tag filter entries are shown down to the depth unless folded otherwise, with one every matching entry is shown with the entries it is under.
+//Completed entries can be hidden unless something below them is still incomplete.
+impl Core {
+    pub(crate) fn view_info(&self) -> Result<Vec<NodeInfo>, Error> {
+        let root = self.current_root();
+
+        let mut below = Vec::new();
+        let child_count = self.view_children(root, 0, &mut below)?;
+
+        //the root is always shown so the list is never empty.
+        let mut info = vec![NodeInfo{id: root, depth: 0, child_count: child_count}];
+        info.append(&mut below);
+
+        Ok(info)
+    }
+
+    ///Hides entries that are complete along with everything below them.
+    pub fn set_hide_completed(&mut self, hide: bool) {
+        if self.hide_completed != hide {
+            self.hide_completed = hide;
+            self.modified = true;
+        }
+    }
+
+    pub fn hide_completed(&self) -> bool {
+        self.hide_completed
+    }
+
+    ///Folds are kept with the list but are not undone.
+    pub fn set_fold(&mut self, id: usize, fold: Fold) -> Result<(), Error> {
+        let entry = self.get_entry_mut(id)?;
+
+        if entry.fold != fold {
+            entry.fold = fold;
+            self.modified = true;
+        }
+
+        Ok(())
+    }
+
+    ///Collapses the entry if its children are shown, otherwise expands it.
+    pub fn toggle_fold(&mut self, id: usize, depth: usize) -> Result<(), Error> {
+        let fold = if self.is_expanded(id, depth)? { Fold::Collapsed } else { Fold::Expanded };
+        self.set_fold(id, fold)
+    }
+
+    ///Collapses or expands every entry below the current root.
+    ///Their folds go back to following the depth, which is set to show only the children of the current root or everything.
+    pub fn fold_all(&mut self, fold: Fold) -> Result<(), Error> {
+        let root = self.current_root();
+
+        for id in self.descendants_of(root)? {
+            self.set_fold(id, Fold::Depth)?;
+        }
+
+        self.depth = match fold {
+            Fold::Expanded => self.height(root)?.max(1),
+            _ => 1,
+        };
+
+        Ok(())
+    }
+
+    ///Number of levels below the entry.
+    fn height(&self, id: usize) -> Result<usize, Error> {
+        let mut height = 0;
+
+        for child in self.children_of(id)? {
+            height = height.max(self.height(child)? + 1);
+        }
+
+        Ok(height)
+    }
+
+    ///Whether the children of the entry at the depth below the current root are shown, ignoring the tag filter.
+    pub(crate) fn is_expanded(&self, id: usize, depth: usize) -> Result<bool, Error> {
+        Ok(match self.get_entry(id)?.fold {
+            Fold::Depth     => depth < self.depth,
+            Fold::Collapsed => false,
+            Fold::Expanded  => true,
+        })
+    }
+
+    ///Adds what is shown below the entry. Returns the number of children the entry is shown with.
+    fn view_children(&self, id: usize, depth: usize, info: &mut Vec<NodeInfo>) -> Result<usize, Error> {
+        //folded entries still show they have children.
+        if self.filter.is_none() && !self.is_expanded(id, depth)? {
+            let mut count = 0;
+
+            for child in self.children_of(id)? {
+                if !self.is_hidden(child)? {
+                    count += 1;
+                }
+            }
+
+            return Ok(count)
+        }
+
+        let mut count = 0;
+
+        for child in self.children_of(id)? {
+            if self.view_entry(child, depth + 1, info)? {
+                count += 1;
+            }
+        }
+
+        Ok(count)
+    }
+
+    ///Adds the entry and what is shown below it. Returns false if the entry is not shown.
+    fn view_entry(&self, id: usize, depth: usize, info: &mut Vec<NodeInfo>) -> Result<bool, Error> {
+        if self.is_hidden(id)? { return Ok(false) }
+
+        let mut below = Vec::new();
+        let child_count = self.view_children(id, depth, &mut below)?;
+
+        if let Some(ref tag) = self.filter {
+            if child_count == 0 && !self.get_entry(id)?.has_tag(tag) { return Ok(false) }
+        }
+
+        info.push(NodeInfo{id: id, depth: depth, child_count: child_count});
+        info.append(&mut below);
+
+        Ok(true)
+    }
+
+    fn is_hidden(&self, id: usize) -> Result<bool, Error> {
+        if !self.hide_completed { return Ok(false) }
+
+        if !self.get_entry(id)?.complete { return Ok(false) }
+
+        for descendant in self.descendants_of(id)? {
+            if !self.get_entry(descendant)?.complete { return Ok(false) }
+        }
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hide_completed() {
+        let mut core = Core::new(String::from("list"));
+        let root = core.current_root();
+
+        let done = core.new_entry(String::from("Done"), Position::LastChild, root).unwrap();
+        core.new_entry(String::from("Done too"), Position::LastChild, done).unwrap();
+        let open = core.new_entry(String::from("Open"), Position::LastChild, root).unwrap();
+        core.set_complete(done).unwrap();
+
+        core.set_hide_completed(true);
+        let ids: Vec<usize> = core.get_entries_info().unwrap().iter().map(|info| info.id).collect();
+        assert_eq!(ids, vec![root, open]);
+    }
+
+    #[test]
+    fn fold() {
+        let mut core = Core::new(String::from("list"));
+        let root = core.current_root();
+        core.dec_depth(1);
+
+        let parent = core.new_entry(String::from("Parent"), Position::LastChild, root).unwrap();
+        let child = core.new_entry(String::from("Child"), Position::LastChild, parent).unwrap();
+        let other = core.new_entry(String::from("Other"), Position::LastChild, root).unwrap();
+        core.new_entry(String::from("Other child"), Position::LastChild, other).unwrap();
+
+        let ids = |core: &Core| core.get_entries_info().unwrap().iter().map(|info| info.id).collect::<Vec<usize>>();
+        assert_eq!(ids(&core), vec![root, parent, other]);
+
+        core.toggle_fold(parent, 1).unwrap();
+        assert_eq!(ids(&core), vec![root, parent, child, other]);
+
+        core.fold_all(Fold::Collapsed).unwrap();
+        assert_eq!(ids(&core), vec![root, parent, other]);
+        assert_eq!(core.get_entries_info().unwrap()[1].child_count, 1);
+
+        core.modified = false;
+        core.reveal(child).unwrap();
+        assert_eq!(ids(&core), vec![root, parent, child, other]);
+        assert_eq!(core.depth(), 1);
+        assert!(!core.is_modified());
+    }
+
+    #[test]
+    fn fold_all_follows_depth() {
+        let mut core = Core::new(String::from("list"));
+        let root = core.current_root();
+
+        let parent = core.new_entry(String::from("Parent"), Position::LastChild, root).unwrap();
+        let child = core.new_entry(String::from("Child"), Position::LastChild, parent).unwrap();
+        let grandchild = core.new_entry(String::from("Grandchild"), Position::LastChild, child).unwrap();
+        core.set_fold(child, Fold::Collapsed).unwrap();
+
+        let ids = |core: &Core| core.get_entries_info().unwrap().iter().map(|info| info.id).collect::<Vec<usize>>();
+
+        core.fold_all(Fold::Expanded).unwrap();
+        assert_eq!(ids(&core), vec![root, parent, child, grandchild]);
+        assert_eq!(core.get_entry(child).unwrap().fold, Fold::Depth);
+
+        //changing the depth still works after expanding everything.
+        core.dec_depth(1);
+        assert_eq!(ids(&core), vec![root, parent, child]);
+
+        core.fold_all(Fold::Collapsed).unwrap();
+        assert_eq!(ids(&core), vec![root, parent]);
+
+        core.inc_depth(1);
+        assert_eq!(ids(&core), vec![root, parent, child]);
+    }
+
+    #[test]
+    fn undo_keeps_fold() {
+        let mut core = Core::new(String::from("list"));
+        let root = core.current_root();
+        let entry = core.new_entry(String::from("Entry"), Position::LastChild, root).unwrap();
+
+        core.rename(entry, String::from("Renamed")).unwrap();
+        core.set_fold(entry, Fold::Collapsed).unwrap();
+
+        core.undo().unwrap();
+        assert_eq!(core.get_entry(entry).unwrap().name, "Entry");
+        assert_eq!(core.get_entry(entry).unwrap().fold, Fold::Collapsed);
+
+        core.redo().unwrap();
+        assert_eq!(core.get_entry(entry).unwrap().fold, Fold::Collapsed);
+    }
+}